@@ -0,0 +1,107 @@
+use std::collections::HashMap;
+
+use crossterm::event::KeyCode;
+
+/// Something a key press can do in the interactive loop.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Action {
+    PanUp,
+    PanDown,
+    PanLeft,
+    PanRight,
+    ZoomIn,
+    ZoomOut,
+    RotateLeft,
+    RotateRight,
+    Quit,
+}
+
+/// Maps keys to actions. Starts from the default bindings, which individual
+/// keys can then be rebound or unbound on.
+#[derive(Debug, Clone)]
+pub struct Controls {
+    bindings: HashMap<KeyCode, Action>,
+}
+
+impl Default for Controls {
+    /// Arrow keys pan, `+` (or `=`) and `-` zoom, `[` and `]` rotate and `q`
+    /// quits.
+    fn default() -> Self {
+        let bindings = [
+            (KeyCode::Up, Action::PanUp),
+            (KeyCode::Down, Action::PanDown),
+            (KeyCode::Left, Action::PanLeft),
+            (KeyCode::Right, Action::PanRight),
+            (KeyCode::Char('+'), Action::ZoomIn),
+            (KeyCode::Char('='), Action::ZoomIn),
+            (KeyCode::Char('-'), Action::ZoomOut),
+            (KeyCode::Char('['), Action::RotateLeft),
+            (KeyCode::Char(']'), Action::RotateRight),
+            (KeyCode::Char('q'), Action::Quit),
+        ];
+
+        Self {
+            bindings: bindings.into_iter().collect(),
+        }
+    }
+}
+
+impl Controls {
+    /// Controls with no keys bound.
+    pub fn empty() -> Self {
+        Self {
+            bindings: HashMap::new(),
+        }
+    }
+
+    /// Makes `key` perform `action`, replacing whatever it did before. Other
+    /// keys bound to the same action keep working.
+    pub fn bind(&mut self, key: KeyCode, action: Action) {
+        self.bindings.insert(key, action);
+    }
+
+    pub fn unbind(&mut self, key: KeyCode) {
+        self.bindings.remove(&key);
+    }
+
+    /// The action bound to `key`, if any.
+    pub fn action(&self, key: KeyCode) -> Option<Action> {
+        self.bindings.get(&key).copied()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn defaults_match_the_demo_keys() {
+        let controls = Controls::default();
+
+        assert_eq!(controls.action(KeyCode::Up), Some(Action::PanUp));
+        assert_eq!(controls.action(KeyCode::Char('=')), Some(Action::ZoomIn));
+        assert_eq!(controls.action(KeyCode::Char('q')), Some(Action::Quit));
+        assert_eq!(controls.action(KeyCode::Char('w')), None);
+    }
+
+    #[test]
+    fn keys_can_be_rebound() {
+        let mut controls = Controls::default();
+        for (key, action) in [
+            ('w', Action::PanUp),
+            ('a', Action::PanLeft),
+            ('s', Action::PanDown),
+            ('d', Action::PanRight),
+        ] {
+            controls.bind(KeyCode::Char(key), action);
+        }
+        controls.bind(KeyCode::Esc, Action::Quit);
+        controls.unbind(KeyCode::Char('q'));
+
+        assert_eq!(controls.action(KeyCode::Char('a')), Some(Action::PanLeft));
+        assert_eq!(controls.action(KeyCode::Left), Some(Action::PanLeft));
+        assert_eq!(controls.action(KeyCode::Esc), Some(Action::Quit));
+        assert_eq!(controls.action(KeyCode::Char('q')), None);
+        assert_eq!(Controls::empty().action(KeyCode::Up), None);
+    }
+}
@@ -1 +1,11 @@
+pub mod controls;
+pub mod renderer;
+pub mod scene;
+pub mod shapes;
+pub mod terminal;
 pub mod vector2;
+
+pub use shapes::{
+    Arc, Circle, Drawable, Dynamic, Ellipse, Group, Line, Polygon, QuadBezier, Rect, Ring, Shape,
+    Sprite, Stroke, Text, Transformed, Triangle,
+};
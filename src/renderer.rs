@@ -0,0 +1,2967 @@
+use std::{
+    any::Any,
+    fmt,
+    io::{stdout, Write},
+    ops::Range,
+    thread,
+    time::{Duration, Instant},
+};
+
+use crossterm::{
+    cursor::{MoveTo, MoveToNextLine},
+    queue,
+    style::{Color, Print, SetBackgroundColor, SetForegroundColor},
+    terminal::{Clear, ClearType},
+};
+
+use crate::{
+    scene::{self, SceneError},
+    shapes::{Drawable, Dynamic, Rect, Shape},
+    vector2::Vector2,
+};
+
+/// Smallest zoom level accepted by `Renderer::set_zoom`.
+pub const MIN_ZOOM: f32 = 0.01;
+
+/// Width and height in cells of every bucket of the spatial grid `render`
+/// uses to skip drawables far from a cell.
+const GRID_BUCKET_SIZE: usize = 8;
+
+/// Characters printed for every cell by `draw`, the cell's character followed
+/// by a space.
+const CHARS_PER_CELL: usize = 2;
+
+/// Ramp used when supersampling without `RendererOptions::ramp`.
+pub const DEFAULT_RAMP: &str = " .:-=+*#%@";
+
+/// The empty Braille pattern, every other pattern adds its dot bits to it.
+const BRAILLE_BLANK: u32 = 0x2800;
+
+/// Offset within the cell and bit of every Braille dot. The left column is
+/// dots 1, 2, 3 and 7, the right column dots 4, 5, 6 and 8, top to bottom.
+const BRAILLE_DOTS: [(f32, f32, u32); 8] = [
+    (0.0, 0.0, 0x01),
+    (0.0, 0.25, 0x02),
+    (0.0, 0.5, 0x04),
+    (0.0, 0.75, 0x40),
+    (0.5, 0.0, 0x08),
+    (0.5, 0.25, 0x10),
+    (0.5, 0.5, 0x20),
+    (0.5, 0.75, 0x80),
+];
+
+pub struct Renderer<'a> {
+    options: RendererOptions,
+    position: Vector2<f32>,
+    zoom: f32,
+    rotation: f32,
+    buffer: Vec<char>,
+    /// See `local_pixels`.
+    cell_positions: Vec<Vector2<f32>>,
+    /// Colors of every cell in `buffer`.
+    styles: Vec<CellStyle>,
+    /// `Drawable::depth` of the shape drawn in every cell of `buffer`,
+    /// infinite for empty cells.
+    depths: Vec<f32>,
+    /// The buffer and styles as of the last `draw`, or `None` if the next
+    /// draw must repaint the whole screen.
+    drawn: Option<Cells>,
+    clip: Option<Rect>,
+    drawables: Vec<(DrawableId, Stored<'a>)>,
+    next_id: u64,
+    stats: RenderStats,
+    /// When the previous `finish_frame` returned.
+    frame_start: Option<Instant>,
+    frame_time: Duration,
+}
+
+/// Handle to a drawable added to a `Renderer`. Ids are never reused, so a
+/// removed drawable's id stays invalid.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct DrawableId(u64);
+
+/// Counters collected by the last `Renderer::render`.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct RenderStats {
+    /// Drawables added to the renderer.
+    pub shapes_total: usize,
+    /// Drawables skipped because their bbox is outside the camera.
+    pub shapes_culled: usize,
+    /// Cell and drawable pairs left to test after culling and the spatial
+    /// grid. Modes sampling several points per cell test each pair more
+    /// than once.
+    pub pixels_tested: usize,
+    pub duration: Duration,
+}
+
+/// A drawable either borrowed from the caller or owned by the renderer.
+enum Stored<'a> {
+    Borrowed(&'a dyn Drawable),
+    Owned(Box<dyn OwnedDrawable>),
+    Dynamic(Box<dyn OwnedDynamic>),
+}
+
+impl Stored<'_> {
+    fn get(&self) -> &dyn Drawable {
+        match self {
+            Stored::Borrowed(drawable) => *drawable,
+            Stored::Owned(drawable) => drawable.as_ref(),
+            Stored::Dynamic(drawable) => drawable.as_ref(),
+        }
+    }
+}
+
+/// Owned drawables keep their concrete type so `get_owned_mut` can downcast
+/// them.
+trait OwnedDrawable: Drawable + Any {}
+
+impl<T: Drawable + Any> OwnedDrawable for T {}
+
+trait OwnedDynamic: OwnedDrawable + Dynamic {}
+
+impl<T: Dynamic + Any> OwnedDynamic for T {}
+
+/// How shapes are turned into characters.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum RenderMode {
+    /// One sample per cell, drawn with the shape's glyph or `fill_char`.
+    #[default]
+    Ascii,
+    /// Two by four samples per cell, drawn as Unicode Braille dots. Glyphs
+    /// and `fill_char` are ignored.
+    Braille,
+    /// Two samples per cell stacked vertically, drawn with the half and full
+    /// block characters. Glyphs and `fill_char` are ignored.
+    HalfBlock,
+}
+
+pub struct RendererOptions {
+    pub viewport_width: usize,
+    pub viewport_height: usize,
+    pub fill_char: char,
+    pub background_char: char,
+    pub mode: RenderMode,
+    /// Characters from least to most covered. When not empty, `Ascii` mode
+    /// picks the character matching each cell's `Drawable::coverage`
+    /// instead of using glyphs and `fill_char`.
+    pub ramp: Vec<char>,
+    /// Samples per row and column of every cell in `Ascii` mode. Above 1,
+    /// each cell's coverage is averaged over the grid of samples and drawn
+    /// with the ramp, or `DEFAULT_RAMP` if none is set.
+    pub samples_per_cell: usize,
+    /// Height of a terminal character divided by its width. Rows are
+    /// sampled further apart as this grows, so shapes keep their proportions
+    /// on screen.
+    pub cell_aspect: f32,
+    /// Frames per second `Renderer::finish_frame` paces the loop to, or
+    /// `None` to not wait at all.
+    pub target_fps: Option<u32>,
+    /// Replaces the bottom row of the drawn output with the camera position,
+    /// zoom and frame rate. The buffer itself is left unchanged.
+    pub show_debug: bool,
+    /// Draws the world x and y axes over the shapes, with `|` and `-` and a
+    /// `+` at the origin.
+    pub show_axes: bool,
+    /// Draws a `.` over the shapes wherever grid lines this many world units
+    /// apart cross, or nothing if `None`.
+    pub grid_spacing: Option<f32>,
+}
+
+impl Default for RendererOptions {
+    fn default() -> Self {
+        Self {
+            viewport_width: 80,
+            viewport_height: 24,
+            fill_char: '#',
+            background_char: ' ',
+            mode: RenderMode::Ascii,
+            ramp: Vec::new(),
+            samples_per_cell: 1,
+            cell_aspect: 2.0,
+            target_fps: None,
+            show_debug: false,
+            show_axes: false,
+            grid_spacing: None,
+        }
+    }
+}
+
+impl RendererOptions {
+    /// Starts from the default options, only the options that differ need
+    /// to be set.
+    pub fn builder() -> RendererOptionsBuilder {
+        RendererOptionsBuilder {
+            options: RendererOptions::default(),
+        }
+    }
+}
+
+pub struct RendererOptionsBuilder {
+    options: RendererOptions,
+}
+
+impl RendererOptionsBuilder {
+    pub fn viewport_width(mut self, width: usize) -> Self {
+        self.options.viewport_width = width;
+        self
+    }
+
+    pub fn viewport_height(mut self, height: usize) -> Self {
+        self.options.viewport_height = height;
+        self
+    }
+
+    pub fn fill_char(mut self, fill_char: char) -> Self {
+        self.options.fill_char = fill_char;
+        self
+    }
+
+    pub fn background_char(mut self, background_char: char) -> Self {
+        self.options.background_char = background_char;
+        self
+    }
+
+    pub fn mode(mut self, mode: RenderMode) -> Self {
+        self.options.mode = mode;
+        self
+    }
+
+    /// Sets the ramp from a string such as `" .:-=+*#%@"`.
+    pub fn ramp(mut self, ramp: &str) -> Self {
+        self.options.ramp = ramp.chars().collect();
+        self
+    }
+
+    pub fn samples_per_cell(mut self, samples: usize) -> Self {
+        self.options.samples_per_cell = samples;
+        self
+    }
+
+    pub fn cell_aspect(mut self, cell_aspect: f32) -> Self {
+        self.options.cell_aspect = cell_aspect;
+        self
+    }
+
+    pub fn target_fps(mut self, fps: u32) -> Self {
+        self.options.target_fps = Some(fps);
+        self
+    }
+
+    pub fn show_debug(mut self, show_debug: bool) -> Self {
+        self.options.show_debug = show_debug;
+        self
+    }
+
+    pub fn show_axes(mut self, show_axes: bool) -> Self {
+        self.options.show_axes = show_axes;
+        self
+    }
+
+    pub fn grid_spacing(mut self, spacing: f32) -> Self {
+        self.options.grid_spacing = Some(spacing);
+        self
+    }
+
+    pub fn build(self) -> RendererOptions {
+        self.options
+    }
+}
+
+impl<'a> Renderer<'a> {
+    pub fn new(options: RendererOptions) -> Self {
+        Self {
+            buffer: vec![options.background_char; options.viewport_width * options.viewport_height],
+            cell_positions: cell_coordinates(options.viewport_width, options.viewport_height),
+            styles: vec![CellStyle::default(); options.viewport_width * options.viewport_height],
+            depths: vec![f32::INFINITY; options.viewport_width * options.viewport_height],
+            position: Vector2(0.0, 0.0),
+            zoom: 1.0,
+            rotation: 0.0,
+            drawn: None,
+            clip: None,
+            drawables: Vec::new(),
+            next_id: 0,
+            stats: RenderStats::default(),
+            frame_start: None,
+            frame_time: Duration::ZERO,
+            options,
+        }
+    }
+
+    /// A `width` by `height` cell renderer with the default options. Nothing
+    /// here touches the terminal, so it can be rendered and compared with
+    /// `to_string` or `lines` in tests without a TTY.
+    pub fn new_headless(width: usize, height: usize) -> Self {
+        Self::new(
+            RendererOptions::builder()
+                .viewport_width(width)
+                .viewport_height(height)
+                .build(),
+        )
+    }
+
+    /// Changes the viewport size, keeping the drawables and camera. The
+    /// buffer is cleared, so `render` must be called before drawing again.
+    pub fn resize(&mut self, width: usize, height: usize) {
+        self.options.viewport_width = width;
+        self.options.viewport_height = height;
+        self.buffer = vec![self.options.background_char; width * height];
+        self.styles = vec![CellStyle::default(); width * height];
+        self.depths = vec![f32::INFINITY; width * height];
+        self.cell_positions = cell_coordinates(width, height);
+        self.drawn = None;
+    }
+
+    /// The world area covered by the viewport: `viewport_width` cells right
+    /// and `viewport_height` rows down from `position`, the top left corner.
+    pub fn bbox(&self) -> Rect {
+        let width = self.options.viewport_width as f32 / self.zoom;
+        let height = self.options.viewport_height as f32 * self.row_height() / self.zoom;
+
+        Rect {
+            position: Vector2(self.position.0, self.position.1 - height),
+            width,
+            height,
+            rotation: 0.0,
+        }
+    }
+
+    fn collides_with_rect(&self, rect: &Rect) -> bool {
+        // World y grows upwards while screen rows grow downwards, so the
+        // viewport covers `viewport_height` rows from `position.1` down to
+        // `position.1 - viewport_height / zoom`. The first column and row are
+        // sampled exactly on the left and top edges, so those edges are
+        // inclusive
+        let viewport = self.bbox();
+        let mut self_left = viewport.position.0;
+        let mut self_right = self_left + viewport.width;
+        let mut self_bottom = viewport.position.1;
+        let mut self_top = self_bottom + viewport.height;
+
+        // A rotated viewport is culled against the box around its corners
+        if self.rotation != 0.0 {
+            let corners = [
+                self.rotate_about_camera(&Vector2(self_left, self_top), self.rotation),
+                self.rotate_about_camera(&Vector2(self_right, self_top), self.rotation),
+                self.rotate_about_camera(&Vector2(self_left, self_bottom), self.rotation),
+                self.rotate_about_camera(&Vector2(self_right, self_bottom), self.rotation),
+            ];
+
+            self_left = corners.iter().map(|c| c.0).fold(f32::INFINITY, f32::min);
+            self_right = corners
+                .iter()
+                .map(|c| c.0)
+                .fold(f32::NEG_INFINITY, f32::max);
+            self_bottom = corners.iter().map(|c| c.1).fold(f32::INFINITY, f32::min);
+            self_top = corners
+                .iter()
+                .map(|c| c.1)
+                .fold(f32::NEG_INFINITY, f32::max);
+        }
+
+        let rect_left = rect.position.0;
+        let rect_right = rect_left + rect.width;
+        let rect_bottom = rect.position.1;
+        let rect_top = rect_bottom + rect.height;
+
+        self_left <= rect_right
+            && rect_left < self_right
+            && self_bottom < rect_top
+            && rect_bottom <= self_top
+    }
+
+    pub fn walk(&mut self, direction: Vector2<f32>, distance: f32) {
+        self.position += direction * distance;
+    }
+
+    /// Moves the camera at `speed` world units per second for `dt`, so the
+    /// distance covered doesn't depend on the frame rate.
+    pub fn walk_with_dt(&mut self, direction: Vector2<f32>, speed: f32, dt: Duration) {
+        self.walk(direction, speed * dt.as_secs_f32());
+    }
+
+    pub fn zoom(&self) -> f32 {
+        self.zoom
+    }
+
+    /// Sets the zoom level, where one character covers `1 / zoom` world
+    /// units. Values below `MIN_ZOOM` are clamped.
+    pub fn set_zoom(&mut self, zoom: f32) {
+        self.zoom = zoom.max(MIN_ZOOM);
+    }
+
+    /// Multiplies the zoom level by `factor`, clamping like `set_zoom`.
+    pub fn zoom_by(&mut self, factor: f32) {
+        self.set_zoom(self.zoom * factor);
+    }
+
+    pub fn rotation(&self) -> f32 {
+        self.rotation
+    }
+
+    /// Sets the camera rotation in radians. A positive rotation turns the
+    /// camera counter-clockwise, so the scene appears to turn clockwise.
+    pub fn set_rotation(&mut self, radians: f32) {
+        self.rotation = radians;
+    }
+
+    pub fn rotate_by(&mut self, radians: f32) {
+        self.rotation += radians;
+    }
+
+    /// Restricts `render` to the cells inside `rect`, leaving the rest of
+    /// the buffer untouched. The rect is in cells rather than world units,
+    /// with `position` at its top left cell and `height` counting rows down.
+    pub fn set_clip(&mut self, rect: Rect) {
+        self.clip = Some(rect);
+    }
+
+    pub fn clear_clip(&mut self) {
+        self.clip = None;
+    }
+
+    pub fn add_drawable<T: Drawable>(&mut self, drawable: &'a T) -> DrawableId {
+        self.push(Stored::Borrowed(drawable))
+    }
+
+    /// Adds a drawable owned by the renderer, so it does not need to be kept
+    /// alive separately.
+    pub fn add_owned(&mut self, drawable: impl Drawable + 'static) -> DrawableId {
+        self.push(Stored::Owned(Box::new(drawable)))
+    }
+
+    /// Like `add_owned`, for drawables that `update` should advance every
+    /// frame.
+    pub fn add_dynamic(&mut self, drawable: impl Dynamic + 'static) -> DrawableId {
+        self.push(Stored::Dynamic(Box::new(drawable)))
+    }
+
+    /// Advances every drawable added with `add_dynamic` by `dt` seconds.
+    /// Call it before `render`.
+    pub fn update(&mut self, dt: f32) {
+        for (_, stored) in self.drawables.iter_mut() {
+            if let Stored::Dynamic(drawable) = stored {
+                drawable.update(dt);
+            }
+        }
+    }
+
+    pub fn get_drawable(&self, id: DrawableId) -> Option<&dyn Drawable> {
+        self.drawables
+            .iter()
+            .find(|(other, _)| *other == id)
+            .map(|(_, stored)| stored.get())
+    }
+
+    /// Mutable access to a drawable added with `add_owned` or `load_scene`.
+    /// Borrowed drawables are shared with the caller and return `None`.
+    pub fn get_drawable_mut(&mut self, id: DrawableId) -> Option<&mut dyn Drawable> {
+        let drawable: &mut dyn Drawable = self.owned_mut(id)?;
+
+        Some(drawable)
+    }
+
+    /// Like `get_drawable_mut`, but returns the concrete shape so its fields
+    /// can be changed. Returns `None` if the drawable is not a `T`.
+    pub fn get_owned_mut<T: Drawable + 'static>(&mut self, id: DrawableId) -> Option<&mut T> {
+        let drawable: &mut dyn Any = self.owned_mut(id)?;
+
+        drawable.downcast_mut()
+    }
+
+    fn owned_mut(&mut self, id: DrawableId) -> Option<&mut dyn OwnedDrawable> {
+        self.drawables
+            .iter_mut()
+            .find(|(other, _)| *other == id)
+            .and_then(|(_, stored)| match stored {
+                Stored::Borrowed(_) => None,
+                Stored::Owned(drawable) => Some(drawable.as_mut()),
+                Stored::Dynamic(drawable) => Some(drawable.as_mut()),
+            })
+    }
+
+    fn push(&mut self, drawable: Stored<'a>) -> DrawableId {
+        let id = DrawableId(self.next_id);
+        self.next_id += 1;
+        self.drawables.push((id, drawable));
+
+        id
+    }
+
+    /// Removes a drawable, returning whether it was still present. The
+    /// remaining drawables keep their order.
+    pub fn remove_drawable(&mut self, id: DrawableId) -> bool {
+        match self.drawables.iter().position(|(other, _)| *other == id) {
+            Some(index) => {
+                self.drawables.remove(index);
+                true
+            }
+            None => false,
+        }
+    }
+
+    pub fn clear_drawables(&mut self) {
+        self.drawables.clear();
+    }
+
+    /// The box around the bbox of every drawable, or `None` if there are
+    /// none.
+    pub fn scene_bounds(&self) -> Option<Rect> {
+        let mut bboxes = self.drawables.iter().map(|(_, stored)| stored.get().bbox());
+        let first = bboxes.next()?;
+
+        Some(bboxes.fold(first, |acc, bbox| acc.union(&bbox)))
+    }
+
+    /// Centers the camera on `scene_bounds` and zooms so the bounds, grown
+    /// by `padding` world units on every side, just fit in the viewport at
+    /// the current rotation. Does nothing if there are no drawables.
+    pub fn fit_to_scene(&mut self, padding: f32) {
+        let Some(bounds) = self.scene_bounds() else {
+            return;
+        };
+
+        // Extent of the padded bounds along the rotated viewport axes
+        let (sin, cos) = self.rotation.sin_cos();
+        let (sin, cos) = (sin.abs(), cos.abs());
+        let width = bounds.width + padding * 2.0;
+        let height = bounds.height + padding * 2.0;
+        let across = width * cos + height * sin;
+        let down = width * sin + height * cos;
+
+        let zoom = (self.options.viewport_width as f32 / across)
+            .min(self.options.viewport_height as f32 * self.row_height() / down);
+        // A scene of a single point fits at any zoom
+        if zoom.is_finite() {
+            self.set_zoom(zoom);
+        }
+
+        // `position` is the top left corner, so step back from the center by
+        // half the viewport
+        let half_viewport = Vector2(
+            self.options.viewport_width as f32 / 2.0 / self.zoom,
+            -(self.options.viewport_height as f32) / 2.0 * self.row_height() / self.zoom,
+        );
+        self.position = bounds.center() - half_viewport.rotate(self.rotation);
+    }
+
+    /// Adds every shape in a JSON scene. The scene is an array of shape
+    /// objects, each tagged with a `type` field. Vectors are written as
+    /// `[x, y]` arrays:
+    ///
+    /// ```json
+    /// [
+    ///   { "type": "rect", "position": [0, 0], "width": 4, "height": 2 },
+    ///   { "type": "rect", "position": [6, 0], "width": 4, "height": 2, "rotation": 0.5 },
+    ///   { "type": "circle", "position": [0, 0], "radius": 3 },
+    ///   { "type": "ring", "position": [0, 0], "inner_radius": 2, "outer_radius": 3 },
+    ///   { "type": "arc", "position": [0, 0], "radius": 3, "start_angle": 0, "end_angle": 1.5, "thickness": 1 },
+    ///   { "type": "ellipse", "position": [0, 0], "rx": 4, "ry": 2 },
+    ///   { "type": "line", "start": [0, 0], "end": [5, 5], "thickness": 1 },
+    ///   { "type": "quad_bezier", "p0": [0, 0], "p1": [2, 4], "p2": [4, 0], "thickness": 1, "segments": 16 },
+    ///   { "type": "triangle", "vertices": [[0, 0], [4, 0], [0, 3]] },
+    ///   { "type": "polygon", "vertices": [[0, 0], [2, 0], [2, 2], [0, 2]] }
+    /// ]
+    /// ```
+    ///
+    /// A rect's `rotation` is optional and defaults to `0`. Unknown fields are
+    /// ignored. Nothing is added if any shape is invalid. `scene::to_json`
+    /// writes scenes in this format.
+    pub fn load_scene(&mut self, json: &str) -> Result<(), SceneError> {
+        for shape in scene::from_json(json)? {
+            match shape {
+                Shape::Rect(rect) => self.add_owned(rect),
+                Shape::Circle(circle) => self.add_owned(circle),
+                Shape::Ring(ring) => self.add_owned(ring),
+                Shape::Arc(arc) => self.add_owned(arc),
+                Shape::Ellipse(ellipse) => self.add_owned(ellipse),
+                Shape::Line(line) => self.add_owned(line),
+                Shape::QuadBezier(bezier) => self.add_owned(bezier),
+                Shape::Triangle(triangle) => self.add_owned(triangle),
+                Shape::Polygon(polygon) => self.add_owned(polygon),
+            };
+        }
+
+        Ok(())
+    }
+
+    /// Cell coordinates of every cell, row by row from the top left like
+    /// the buffer. Computed by `new` and `resize` rather than every frame.
+    fn local_pixels(&self) -> &[Vector2<f32>] {
+        &self.cell_positions
+    }
+
+    /// The world position sampled for every cell, in the same order as
+    /// `local_pixels`.
+    pub fn global_pixels(&self) -> Vec<Vector2<f32>> {
+        self.local_pixels()
+            .iter()
+            .map(|point| self.global_position_of(point))
+            .collect()
+    }
+
+    fn global_position_of(&self, point: &Vector2<f32>) -> Vector2<f32> {
+        let unrotated = Vector2(
+            self.position.0 + point.0 / self.zoom,
+            self.position.1 - point.1 * self.row_height() / self.zoom,
+        );
+
+        self.rotate_about_camera(&unrotated, self.rotation)
+    }
+
+    /// The cell coordinates sampling a world point, the inverse of
+    /// `global_position_of`.
+    fn local_position_of(&self, point: &Vector2<f32>) -> Vector2<f32> {
+        let unrotated = self.rotate_about_camera(point, -self.rotation);
+
+        Vector2(
+            (unrotated.0 - self.position.0) * self.zoom,
+            (self.position.1 - unrotated.1) * self.zoom / self.row_height(),
+        )
+    }
+
+    /// The world position sampled by the cell in column `x` and row `y` of
+    /// the viewport. Cells are `CHARS_PER_CELL` terminal columns wide, so
+    /// terminal columns such as mouse positions must be divided by two
+    /// first.
+    pub fn screen_to_world(&self, x: usize, y: usize) -> Vector2<f32> {
+        self.global_position_of(&Vector2(x as f32, y as f32))
+    }
+
+    /// The column and row of the cell showing a world position, or `None`
+    /// if it is outside the viewport. The inverse of `screen_to_world`.
+    pub fn world_to_screen(&self, p: &Vector2<f32>) -> Option<(usize, usize)> {
+        let cell = self.local_position_of(p).floor();
+
+        self.try_index(&cell)
+            .map(|_| (cell.0 as usize, cell.1 as usize))
+    }
+
+    /// The drawable under the cell in column `x` and row `y`, such as the
+    /// cell a mouse click landed on. Where several overlap, returns the one
+    /// drawn in front: the lowest depth, then the highest z, then the last
+    /// added.
+    pub fn pick(&self, screen_x: usize, screen_y: usize) -> Option<DrawableId> {
+        let point = self.screen_to_world(screen_x, screen_y);
+        let mut hits: Vec<_> = self
+            .drawables
+            .iter()
+            .filter(|(_, stored)| stored.get().point_in_self(&point))
+            .collect();
+        // Stable sort so equal z values keep their insertion order, as in
+        // `visible_drawables`
+        hits.sort_by_key(|(_, stored)| stored.get().z());
+
+        let shapes: Vec<_> = hits.iter().map(|(_, stored)| stored.get()).collect();
+        in_front(&shapes, 0..shapes.len()).map(|index| hits[index].0)
+    }
+
+    /// World units covered by one row at zoom 1, relative to the one unit
+    /// covered by a cell's width. Cells are drawn `CHARS_PER_CELL` characters
+    /// wide, which cancels out most of a character's height.
+    fn row_height(&self) -> f32 {
+        self.options.cell_aspect / CHARS_PER_CELL as f32
+    }
+
+    /// Rotates a world point counter-clockwise around the camera position.
+    /// Passing `-self.rotation` undoes the camera rotation, mapping a sampled
+    /// point back into the unrotated view.
+    fn rotate_about_camera(&self, point: &Vector2<f32>, radians: f32) -> Vector2<f32> {
+        point.rotate_around(&self.position, radians)
+    }
+
+    /// Writes a character directly into the buffer. Cells outside the
+    /// viewport are ignored. The next `render` clears the buffer, so call
+    /// this after rendering.
+    pub fn set_pixel(&mut self, x: usize, y: usize, c: char) {
+        if let Some(index) = self.pixel_index(x, y) {
+            self.buffer[index] = c;
+            self.styles[index] = CellStyle::default();
+        }
+    }
+
+    pub fn get_pixel(&self, x: usize, y: usize) -> Option<char> {
+        self.pixel_index(x, y).map(|index| self.buffer[index])
+    }
+
+    /// Plots a one cell wide line between two cells with Bresenham's
+    /// algorithm, both ends included. Like `set_pixel`, cells outside the
+    /// viewport are skipped.
+    pub fn draw_line(&mut self, from: (usize, usize), to: (usize, usize), c: char) {
+        let (mut x, mut y) = (from.0 as isize, from.1 as isize);
+        let (end_x, end_y) = (to.0 as isize, to.1 as isize);
+        let dx = (end_x - x).abs();
+        let dy = -(end_y - y).abs();
+        let step_x = if x < end_x { 1 } else { -1 };
+        let step_y = if y < end_y { 1 } else { -1 };
+        let mut error = dx + dy;
+
+        loop {
+            self.set_pixel(x as usize, y as usize, c);
+            if x == end_x && y == end_y {
+                break;
+            }
+
+            let doubled = 2 * error;
+            if doubled >= dy {
+                error += dy;
+                x += step_x;
+            }
+            if doubled <= dx {
+                error += dx;
+                y += step_y;
+            }
+        }
+    }
+
+    /// Fills every cell within `radius` cells of `center`, measured in
+    /// cells. Rows are filled as spans whose half-width is walked inwards
+    /// with the midpoint circle algorithm, so the cost follows the circle's
+    /// area. Cells outside the viewport are skipped.
+    pub fn fill_circle(&mut self, center: (usize, usize), radius: usize, c: char) {
+        let (cx, cy) = (center.0 as isize, center.1 as isize);
+        let radius = radius as isize;
+        let mut x = radius;
+        // x * x + y * y - radius * radius for the current span end
+        let mut error = 0;
+
+        for y in 0..=radius {
+            while error > 0 {
+                error -= 2 * x - 1;
+                x -= 1;
+            }
+
+            self.fill_span(cy - y, cx - x, cx + x, c);
+            if y != 0 {
+                self.fill_span(cy + y, cx - x, cx + x, c);
+            }
+
+            error += 2 * y + 1;
+        }
+    }
+
+    /// Fills row `y` from `from` to `to` inclusive, clipped to the viewport.
+    fn fill_span(&mut self, y: isize, from: isize, to: isize, c: char) {
+        let width = self.options.viewport_width as isize;
+        if y < 0 || y >= self.options.viewport_height as isize || to < 0 || from >= width {
+            return;
+        }
+
+        let row = y as usize * self.options.viewport_width;
+        let from = from.max(0) as usize;
+        let to = to.min(width - 1) as usize;
+        self.buffer[row + from..=row + to].fill(c);
+        self.styles[row + from..=row + to].fill(CellStyle::default());
+    }
+
+    fn pixel_index(&self, x: usize, y: usize) -> Option<usize> {
+        if x < self.options.viewport_width && y < self.options.viewport_height {
+            Some(x + y * self.options.viewport_width)
+        } else {
+            None
+        }
+    }
+
+    /// Every cell of the buffer with its column and row, row by row from
+    /// the top left.
+    pub fn pixels(&self) -> impl Iterator<Item = (usize, usize, char)> + '_ {
+        let width = self.options.viewport_width;
+
+        self.buffer
+            .iter()
+            .enumerate()
+            .map(move |(index, c)| (index % width, index / width, *c))
+    }
+
+    /// Replaces every cell of the buffer with the result of `f`, called once
+    /// per cell with its column, row and current character. Runs on the
+    /// final buffer, so call it after `render` and before `draw`.
+    pub fn apply_shader(&mut self, f: impl Fn(usize, usize, char) -> char) {
+        let width = self.options.viewport_width;
+        for (index, c) in self.buffer.iter_mut().enumerate() {
+            *c = f(index % width, index / width, *c);
+        }
+    }
+
+    /// Writes the buffer as a binary PPM (P6) image, every cell drawn as a
+    /// `scale` by `scale` block of pixels. Cells holding anything but the
+    /// `background_char` are white, the rest black.
+    pub fn export_ppm<W: Write>(&self, w: &mut W, scale: usize) -> std::io::Result<()> {
+        let width = self.options.viewport_width;
+        write!(
+            w,
+            "P6\n{} {}\n255\n",
+            width * scale,
+            self.options.viewport_height * scale
+        )?;
+
+        let mut row = Vec::with_capacity(width * scale * 3);
+        for cells in self.buffer.chunks(width.max(1)) {
+            row.clear();
+            for c in cells {
+                let value = if *c == self.options.background_char {
+                    0
+                } else {
+                    255
+                };
+                row.extend(std::iter::repeat_n(value, scale * 3));
+            }
+            for _ in 0..scale {
+                w.write_all(&row)?;
+            }
+        }
+
+        w.flush()
+    }
+
+    pub fn lines(&self) -> Vec<String> {
+        let mut strings = Vec::new();
+        let mut i = 0;
+
+        while i < self.options.viewport_width * self.options.viewport_height {
+            strings.push(
+                self.buffer[i..i + self.options.viewport_width]
+                    .iter()
+                    .collect(),
+            );
+            i += self.options.viewport_width;
+        }
+
+        strings
+    }
+
+    /// Index of the cell containing `point`, which may lie anywhere inside
+    /// the cell rather than only on its corner.
+    fn index_f32(&self, point: &Vector2<f32>) -> usize {
+        point.0 as usize + point.1 as usize * self.options.viewport_width
+    }
+
+    pub fn try_index(&self, point: &Vector2<f32>) -> Option<usize> {
+        let in_x = (0.0..self.options.viewport_width as f32).contains(&point.0);
+        let in_y = (0.0..self.options.viewport_height as f32).contains(&point.1);
+
+        if in_x && in_y {
+            Some(self.index_f32(point))
+        } else {
+            None
+        }
+    }
+
+    pub fn render(&mut self) {
+        let start = Instant::now();
+        // Taken so the drawables can stay borrowed while the buffer is
+        // written
+        let mut cells = Cells {
+            chars: std::mem::take(&mut self.buffer),
+            styles: std::mem::take(&mut self.styles),
+        };
+        let mut depths = std::mem::take(&mut self.depths);
+        match &self.clip {
+            Some(_) => {
+                for point in self.render_cells() {
+                    if let Some(index) = self.try_index(point) {
+                        cells.set(index, self.options.background_char, CellStyle::default());
+                        depths[index] = f32::INFINITY;
+                    }
+                }
+            }
+            None => {
+                cells.chars.fill(self.options.background_char);
+                cells.styles.fill(CellStyle::default());
+                depths.fill(f32::INFINITY);
+            }
+        }
+
+        let visible = self.visible_drawables();
+        let shapes_culled = self.drawables.len() - visible.len();
+        let grid = self.spatial_grid(visible);
+        match self.options.mode {
+            RenderMode::Ascii
+                if !self.options.ramp.is_empty() || self.options.samples_per_cell > 1 =>
+            {
+                self.render_ramp(&grid, &mut cells)
+            }
+            RenderMode::Ascii => self.render_ascii(&grid, &mut cells, &mut depths),
+            RenderMode::Braille => self.render_braille(&grid, &mut cells),
+            RenderMode::HalfBlock => self.render_half_block(&grid, &mut cells),
+        }
+        self.render_overlay(&mut cells);
+        self.render_stamped(&mut cells);
+
+        let pixels_tested = self.render_cells().map(|point| grid.at(point).len()).sum();
+        self.stats = RenderStats {
+            shapes_total: self.drawables.len(),
+            shapes_culled,
+            pixels_tested,
+            duration: start.elapsed(),
+        };
+
+        self.buffer = cells.chars;
+        self.styles = cells.styles;
+        self.depths = depths;
+    }
+
+    /// Statistics of the last `render`, all zero before the first one.
+    pub fn last_stats(&self) -> RenderStats {
+        self.stats
+    }
+
+    /// Cells written by `render`, every cell unless a clip is set.
+    fn render_cells(&self) -> impl Iterator<Item = &Vector2<f32>> {
+        self.local_pixels().iter().filter(|cell| {
+            self.clip
+                .as_ref()
+                .is_none_or(|clip| clip.contains_point(cell))
+        })
+    }
+
+    /// Stamps the text of every drawable with `Drawable::text` or
+    /// `Drawable::sprite`, in drawing order. They are not culled by their
+    /// bbox as they keep their size at any zoom, characters outside the
+    /// viewport or clip are skipped instead.
+    fn render_stamped(&self, cells: &mut Cells) {
+        let mut stamped: Vec<_> = self
+            .drawables
+            .iter()
+            .map(|(_, stored)| stored.get())
+            .filter(|shape| shape.text().is_some() || shape.sprite().is_some())
+            .collect();
+        stamped.sort_by_key(|shape| shape.z());
+
+        for shape in stamped {
+            if let Some((position, content)) = shape.text() {
+                self.stamp(cells, shape, position, [content], false);
+            }
+            if let Some((position, rows)) = shape.sprite() {
+                self.stamp(
+                    cells,
+                    shape,
+                    position,
+                    rows.iter().map(String::as_str),
+                    true,
+                );
+            }
+        }
+    }
+
+    /// Writes `rows` top to bottom from the cell showing `position`. With
+    /// `transparent_spaces`, spaces leave the cell below unchanged.
+    fn stamp<'r>(
+        &self,
+        cells: &mut Cells,
+        shape: &dyn Drawable,
+        position: &Vector2<f32>,
+        rows: impl IntoIterator<Item = &'r str>,
+        transparent_spaces: bool,
+    ) {
+        let start = self.local_position_of(position);
+        for (y, row) in rows.into_iter().enumerate() {
+            for (x, c) in row.chars().enumerate() {
+                if transparent_spaces && c == ' ' {
+                    continue;
+                }
+                let cell = Vector2(start.0.floor() + x as f32, start.1.floor() + y as f32);
+                if self
+                    .clip
+                    .as_ref()
+                    .is_some_and(|clip| !clip.contains_point(&cell))
+                {
+                    continue;
+                }
+                if let Some(index) = self.try_index(&cell) {
+                    cells.set(index, c, CellStyle::of(shape));
+                }
+            }
+        }
+    }
+
+    /// Drawables whose bbox collides with the camera, ordered so that later
+    /// (higher z) drawables are drawn over earlier ones.
+    fn visible_drawables(&self) -> Vec<&dyn Drawable> {
+        let mut shapes: Vec<_> = self
+            .drawables
+            .iter()
+            .map(|(_, stored)| stored.get())
+            .filter(|shape| self.collides_with_rect(&shape.bbox()))
+            .collect();
+
+        // Stable sort so equal z values keep their insertion order
+        shapes.sort_by_key(|shape| shape.z());
+        shapes
+    }
+
+    /// Buckets the drawables by the viewport cells their bbox may cover.
+    /// Each bucket keeps the drawables in drawing order.
+    fn spatial_grid<'s>(&self, shapes: Vec<&'s dyn Drawable>) -> SpatialGrid<'s> {
+        let width = self.options.viewport_width;
+        let height = self.options.viewport_height;
+        let columns = width.div_ceil(GRID_BUCKET_SIZE);
+        let rows = height.div_ceil(GRID_BUCKET_SIZE);
+        let mut buckets = vec![Vec::new(); columns * rows];
+        // An empty viewport has no buckets, and no last column or row below
+        if width == 0 || height == 0 {
+            return SpatialGrid { columns, buckets };
+        }
+
+        for shape in shapes {
+            let bbox = shape.bbox();
+            let corners = [
+                Vector2(bbox.position.0, bbox.position.1),
+                Vector2(bbox.position.0 + bbox.width, bbox.position.1),
+                Vector2(bbox.position.0, bbox.position.1 + bbox.height),
+                Vector2(bbox.position.0 + bbox.width, bbox.position.1 + bbox.height),
+            ]
+            .map(|corner| self.local_position_of(&corner));
+
+            // Sub-cell samples reach up to a cell past the sampled corner, so
+            // the covered cells are widened by one on each side
+            let min_x = corners.iter().map(|c| c.0).fold(f32::INFINITY, f32::min) - 1.0;
+            let max_x = corners
+                .iter()
+                .map(|c| c.0)
+                .fold(f32::NEG_INFINITY, f32::max)
+                + 1.0;
+            let min_y = corners.iter().map(|c| c.1).fold(f32::INFINITY, f32::min) - 1.0;
+            let max_y = corners
+                .iter()
+                .map(|c| c.1)
+                .fold(f32::NEG_INFINITY, f32::max)
+                + 1.0;
+            if max_x < 0.0 || max_y < 0.0 || min_x >= width as f32 || min_y >= height as f32 {
+                continue;
+            }
+
+            let first_column = min_x.max(0.0) as usize / GRID_BUCKET_SIZE;
+            let last_column = (max_x as usize).min(width - 1) / GRID_BUCKET_SIZE;
+            let first_row = min_y.max(0.0) as usize / GRID_BUCKET_SIZE;
+            let last_row = (max_y as usize).min(height - 1) / GRID_BUCKET_SIZE;
+            for row in first_row..=last_row {
+                for column in first_column..=last_column {
+                    buckets[row * columns + column].push(shape);
+                }
+            }
+        }
+
+        SpatialGrid { columns, buckets }
+    }
+
+    /// Every shape containing the cell is depth tested against `depths`,
+    /// the last one passing draws it. If that shape is translucent, the cell
+    /// instead shows the `DEFAULT_RAMP` character for the summed alpha of
+    /// the shapes containing it at that depth. Shapes behind it are hidden.
+    fn render_ascii(&self, grid: &SpatialGrid, cells: &mut Cells, depths: &mut [f32]) {
+        let ramp: Vec<char> = DEFAULT_RAMP.chars().collect();
+
+        for point in self.render_cells() {
+            let Some(index) = self.try_index(point) else {
+                continue;
+            };
+            let shapes = grid.at(point);
+            let global_pos = self.global_position_of(point);
+            let mut top = None;
+            let mut alpha = 0.0;
+            for shape in shapes.iter() {
+                if !shape.point_in_self(&global_pos) {
+                    continue;
+                }
+
+                // Equal depths pass, so later shapes still win ties
+                let depth = shape.depth();
+                if depth > depths[index] {
+                    continue;
+                }
+                if depth < depths[index] {
+                    alpha = 0.0;
+                }
+                alpha += shape.alpha().clamp(0.0, 1.0);
+                depths[index] = depth;
+                top = Some(*shape);
+            }
+
+            if let Some(shape) = top {
+                let c = if shape.alpha() < 1.0 {
+                    let step = (alpha.min(1.0) * (ramp.len() - 1) as f32).round();
+                    ramp[step as usize]
+                } else {
+                    shape.glyph().unwrap_or(self.options.fill_char)
+                };
+                cells.set(index, c, CellStyle::of(shape));
+            }
+        }
+    }
+
+    /// Picks a ramp character from the highest coverage of any shape,
+    /// averaged over `samples_per_cell` squared samples. Only the nearest
+    /// shapes covering a sample count towards it, as in `render_ascii`.
+    /// Uncovered cells keep the background. The cell takes the color of the
+    /// shape covering the most of it.
+    fn render_ramp(&self, grid: &SpatialGrid, cells: &mut Cells) {
+        let default_ramp: Vec<char>;
+        let ramp = if self.options.ramp.is_empty() {
+            default_ramp = DEFAULT_RAMP.chars().collect();
+            &default_ramp
+        } else {
+            &self.options.ramp
+        };
+
+        let samples = self.options.samples_per_cell.max(1);
+        let offsets: Vec<f32> = (0..samples).map(|i| i as f32 / samples as f32).collect();
+        // Coverage summed over the samples of a cell for every shape in its
+        // bucket, reused between cells
+        let mut per_shape = Vec::new();
+        // Coverage of the current sample by every shape in the bucket
+        let mut coverages = Vec::new();
+
+        for point in self.render_cells() {
+            let shapes = grid.at(point);
+            let mut total = 0.0;
+            per_shape.clear();
+            per_shape.resize(shapes.len(), 0.0);
+            for dy in offsets.iter() {
+                for dx in offsets.iter() {
+                    let global_pos = self.global_position_of(&Vector2(point.0 + dx, point.1 + dy));
+                    coverages.clear();
+                    coverages.extend(
+                        shapes
+                            .iter()
+                            .map(|shape| shape.coverage(&global_pos).min(1.0)),
+                    );
+                    let front = shapes
+                        .iter()
+                        .zip(coverages.iter())
+                        .filter(|(_, coverage)| **coverage > 0.0)
+                        .map(|(shape, _)| shape.depth())
+                        .fold(f32::INFINITY, f32::min);
+
+                    let mut highest: f32 = 0.0;
+                    let sums = per_shape.iter_mut();
+                    for ((shape, coverage), sum) in shapes.iter().zip(coverages.iter()).zip(sums) {
+                        if shape.depth() == front {
+                            *sum += coverage;
+                            highest = highest.max(*coverage);
+                        }
+                    }
+                    total += highest;
+                }
+            }
+            let coverage = total / (samples * samples) as f32;
+
+            if coverage > 0.0 {
+                if let Some(index) = self.try_index(point) {
+                    let step = (coverage * (ramp.len() - 1) as f32).round();
+                    // Later shapes win ties, as they are drawn on top
+                    let style = per_shape
+                        .iter()
+                        .zip(shapes)
+                        .max_by(|(a, _), (b, _)| a.total_cmp(b))
+                        .map(|(_, shape)| CellStyle::of(*shape))
+                        .unwrap_or_default();
+                    cells.set(index, ramp[step as usize], style);
+                }
+            }
+        }
+    }
+
+    /// The cell takes the color of the highest shape covering any dot.
+    fn render_braille(&self, grid: &SpatialGrid, cells: &mut Cells) {
+        for point in self.render_cells() {
+            let shapes = grid.at(point);
+            let mut dots = 0;
+            let mut top = None;
+            for (dx, dy, bit) in BRAILLE_DOTS {
+                let global_pos = self.global_position_of(&Vector2(point.0 + dx, point.1 + dy));
+                if let Some(hit) = topmost(shapes, &global_pos) {
+                    dots |= bit;
+                    top = in_front(shapes, top.into_iter().chain([hit]));
+                }
+            }
+
+            if let (Some(top), Some(index), Some(c)) = (
+                top,
+                self.try_index(point),
+                char::from_u32(BRAILLE_BLANK + dots),
+            ) {
+                cells.set(index, c, CellStyle::of(shapes[top]));
+            }
+        }
+    }
+
+    /// The cell takes the color of the highest shape covering either half.
+    fn render_half_block(&self, grid: &SpatialGrid, cells: &mut Cells) {
+        for point in self.render_cells() {
+            let shapes = grid.at(point);
+            let top = topmost(shapes, &self.global_position_of(point));
+            let bottom = topmost(
+                shapes,
+                &self.global_position_of(&Vector2(point.0, point.1 + 0.5)),
+            );
+
+            let c = match (top, bottom) {
+                (Some(_), Some(_)) => '\u{2588}',
+                (Some(_), None) => '\u{2580}',
+                (None, Some(_)) => '\u{2584}',
+                (None, None) => continue,
+            };
+            let hit = in_front(shapes, top.into_iter().chain(bottom));
+            if let (Some(hit), Some(index)) = (hit, self.try_index(point)) {
+                cells.set(index, c, CellStyle::of(shapes[hit]));
+            }
+        }
+    }
+
+    /// Draws the axes and grid over the shapes. A cell shows a line if the
+    /// line passes within half a cell of its sample point.
+    fn render_overlay(&self, cells: &mut Cells) {
+        let spacing = self.options.grid_spacing.filter(|spacing| *spacing > 0.0);
+        if !self.options.show_axes && spacing.is_none() {
+            return;
+        }
+
+        // Half the extent of a cell along each world axis, which grows as
+        // the camera rotates
+        let origin = self.global_position_of(&Vector2::ZERO);
+        let across = self.global_position_of(&Vector2(1.0, 0.0)) - origin.clone();
+        let down = self.global_position_of(&Vector2(0.0, 1.0)) - origin;
+        let half = Vector2(across.0.abs() + down.0.abs(), across.1.abs() + down.1.abs()) / 2.0;
+
+        for point in self.render_cells() {
+            let sample = self.global_position_of(point);
+            let near = |axis: usize| sample[axis] - half[axis]..sample[axis] + half[axis];
+            let on_axis = |axis: usize| near(axis).contains(&0.0);
+            // The first grid line at or past the start of the range
+            let on_line = |axis: usize, spacing: f32| {
+                let range = near(axis);
+                range.contains(&((range.start / spacing).ceil() * spacing))
+            };
+
+            let c = match (self.options.show_axes, on_axis(0), on_axis(1)) {
+                (true, true, true) => '+',
+                (true, true, false) => '|',
+                (true, false, true) => '-',
+                _ => match spacing {
+                    Some(spacing) if on_line(0, spacing) && on_line(1, spacing) => '.',
+                    _ => continue,
+                },
+            };
+            if let Some(index) = self.try_index(point) {
+                cells.set(index, c, CellStyle::default());
+            }
+        }
+    }
+
+    /// Renders the buffer with a space after every character, matching the
+    /// double-width output of `draw`.
+    pub fn to_string_spaced(&self) -> String {
+        self.lines()
+            .iter()
+            .map(|line| spaced(line))
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    pub fn draw_standard_terminal(&self) -> std::io::Result<()> {
+        let mut stdout = stdout();
+
+        queue!(stdout, Clear(ClearType::All))?;
+
+        for line in self.lines() {
+            let mut out = spaced(&line);
+            out.push('\n');
+
+            queue!(stdout, Print(out))?;
+        }
+
+        stdout.flush()?;
+
+        Ok(())
+    }
+
+    pub fn draw(&mut self) -> std::io::Result<()> {
+        self.draw_to(&mut stdout().lock())
+    }
+
+    /// Writes the buffer to `w`. Only cells that changed since the previous
+    /// draw are written, the screen is cleared and fully repainted on the
+    /// first draw and after a `resize` or `redraw_all`. Consecutive cells of
+    /// the same color are printed together.
+    pub fn draw_to<W: Write>(&mut self, w: &mut W) -> std::io::Result<()> {
+        let frame = self.frame();
+        match &self.drawn {
+            Some(drawn) if drawn.chars.len() == frame.chars.len() => {
+                self.draw_changes(w, &frame, drawn)?
+            }
+            _ => self.draw_all(w, &frame)?,
+        }
+
+        self.drawn = Some(frame);
+        w.flush()
+    }
+
+    /// Makes the next draw repaint the whole screen, for when the terminal
+    /// was changed by something else.
+    pub fn redraw_all(&mut self) {
+        self.drawn = None;
+    }
+
+    /// Ends a frame of the main loop. With a `target_fps`, sleeps for
+    /// whatever is left of the frame's budget since the previous call.
+    /// Returns the time the whole frame took, including the sleep, which
+    /// is zero for the first frame.
+    pub fn finish_frame(&mut self) -> Duration {
+        if let (Some(fps), Some(start)) = (self.options.target_fps, self.frame_start) {
+            if fps > 0 {
+                let budget = Duration::from_secs(1) / fps;
+                if let Some(remaining) = budget.checked_sub(start.elapsed()) {
+                    thread::sleep(remaining);
+                }
+            }
+        }
+
+        let end = Instant::now();
+        if let Some(start) = self.frame_start {
+            self.frame_time = end - start;
+        }
+        self.frame_start = Some(end);
+
+        self.frame_time
+    }
+
+    /// How long the last frame took, as returned by `finish_frame`.
+    pub fn last_frame_time(&self) -> Duration {
+        self.frame_time
+    }
+
+    /// The cells to draw, the buffer with the debug line on top when
+    /// `show_debug` is set.
+    fn frame(&self) -> Cells {
+        let mut frame = Cells {
+            chars: self.buffer.clone(),
+            styles: self.styles.clone(),
+        };
+
+        let width = self.options.viewport_width;
+        if self.options.show_debug && self.options.viewport_height > 0 {
+            let fps = match self.frame_time.as_secs_f32() {
+                0.0 => 0.0,
+                secs => 1.0 / secs,
+            };
+            let status = format!(
+                "pos={:.1} zoom={:.2} fps={:.0}",
+                self.position, self.zoom, fps
+            );
+            let last_row = (self.options.viewport_height - 1) * width;
+            let mut text = status.chars();
+            for index in last_row..last_row + width {
+                let c = text.next().unwrap_or(self.options.background_char);
+                frame.set(index, c, CellStyle::default());
+            }
+        }
+
+        frame
+    }
+
+    fn draw_all<W: Write>(&self, w: &mut W, frame: &Cells) -> std::io::Result<()> {
+        queue!(w, Clear(ClearType::All), MoveTo(0, 0))?;
+
+        let width = self.options.viewport_width;
+        let mut style = CellStyle::default();
+        for y in 0..self.options.viewport_height {
+            let row = y * width..(y + 1) * width;
+            self.print_run(w, frame, row, &mut style)?;
+            reset_style(w, &mut style)?;
+            queue!(w, MoveToNextLine(1))?;
+        }
+
+        Ok(())
+    }
+
+    /// Prints every run of changed cells with a single cursor move.
+    fn draw_changes<W: Write>(
+        &self,
+        w: &mut W,
+        frame: &Cells,
+        drawn: &Cells,
+    ) -> std::io::Result<()> {
+        let width = self.options.viewport_width;
+        let changed = |index: usize| {
+            frame.chars[index] != drawn.chars[index] || frame.styles[index] != drawn.styles[index]
+        };
+        let mut style = CellStyle::default();
+
+        for y in 0..self.options.viewport_height {
+            let mut x = 0;
+            while x < width {
+                if !changed(y * width + x) {
+                    x += 1;
+                    continue;
+                }
+
+                let start = x;
+                while x < width && changed(y * width + x) {
+                    x += 1;
+                }
+
+                queue!(w, MoveTo((start * CHARS_PER_CELL) as u16, y as u16))?;
+                self.print_run(w, frame, y * width + start..y * width + x, &mut style)?;
+            }
+            reset_style(w, &mut style)?;
+        }
+
+        Ok(())
+    }
+
+    /// Prints the cells in `range`, switching colors only where they change.
+    /// `style` holds the colors currently set on the terminal.
+    fn print_run<W: Write>(
+        &self,
+        w: &mut W,
+        frame: &Cells,
+        range: Range<usize>,
+        style: &mut CellStyle,
+    ) -> std::io::Result<()> {
+        let mut start = range.start;
+        while start < range.end {
+            let run_style = frame.styles[start];
+            let end = (start..range.end)
+                .find(|&index| frame.styles[index] != run_style)
+                .unwrap_or(range.end);
+
+            if run_style.fg != style.fg {
+                queue!(w, SetForegroundColor(run_style.fg.unwrap_or(Color::Reset)))?;
+            }
+            if run_style.bg != style.bg {
+                queue!(w, SetBackgroundColor(run_style.bg.unwrap_or(Color::Reset)))?;
+            }
+            *style = run_style;
+            let text: String = frame.chars[start..end].iter().collect();
+            queue!(w, Print(spaced(&text)))?;
+
+            start = end;
+        }
+
+        Ok(())
+    }
+}
+
+impl fmt::Display for Renderer<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.lines().join("\n"))
+    }
+}
+
+/// Drawables grouped into square buckets of `GRID_BUCKET_SIZE` cells.
+struct SpatialGrid<'s> {
+    columns: usize,
+    buckets: Vec<Vec<&'s dyn Drawable>>,
+}
+
+impl<'s> SpatialGrid<'s> {
+    /// The drawables that may cover the cell at `point`.
+    fn at(&self, point: &Vector2<f32>) -> &[&'s dyn Drawable] {
+        let column = point.0 as usize / GRID_BUCKET_SIZE;
+        let row = point.1 as usize / GRID_BUCKET_SIZE;
+        if column >= self.columns {
+            return &[];
+        }
+
+        self.buckets
+            .get(row * self.columns + column)
+            .map_or(&[], Vec::as_slice)
+    }
+}
+
+/// Colors of a single cell, `None` for the terminal's defaults.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+struct CellStyle {
+    fg: Option<Color>,
+    bg: Option<Color>,
+}
+
+impl CellStyle {
+    fn of(shape: &dyn Drawable) -> Self {
+        Self {
+            fg: shape.color(),
+            bg: shape.bg_color(),
+        }
+    }
+}
+
+/// The characters and styles of every cell. A render pass takes them out of
+/// the renderer so the drawables can stay borrowed meanwhile.
+struct Cells {
+    chars: Vec<char>,
+    styles: Vec<CellStyle>,
+}
+
+impl Cells {
+    fn set(&mut self, index: usize, c: char, style: CellStyle) {
+        self.chars[index] = c;
+        self.styles[index] = style;
+    }
+}
+
+fn cell_coordinates(width: usize, height: usize) -> Vec<Vector2<f32>> {
+    let mut pixels = Vec::with_capacity(width * height);
+
+    for y in 0..height {
+        for x in 0..width {
+            pixels.push(Vector2(x as f32, y as f32));
+        }
+    }
+
+    pixels
+}
+
+/// Index of the shape containing the point that is drawn in front.
+fn topmost(shapes: &[&dyn Drawable], point: &Vector2<f32>) -> Option<usize> {
+    in_front(
+        shapes,
+        (0..shapes.len()).filter(|&index| shapes[index].point_in_self(point)),
+    )
+}
+
+/// The candidate drawn in front, the one with the lowest depth or, between
+/// equal depths, the one drawn last.
+fn in_front(shapes: &[&dyn Drawable], candidates: impl Iterator<Item = usize>) -> Option<usize> {
+    candidates.max_by(|&a, &b| {
+        shapes[b]
+            .depth()
+            .total_cmp(&shapes[a].depth())
+            .then(a.cmp(&b))
+    })
+}
+
+/// Returns the terminal to its default colors, so a background color does
+/// not spill past the end of a line.
+fn reset_style<W: Write>(w: &mut W, style: &mut CellStyle) -> std::io::Result<()> {
+    if style.fg.is_some() {
+        queue!(w, SetForegroundColor(Color::Reset))?;
+    }
+    if style.bg.is_some() {
+        queue!(w, SetBackgroundColor(Color::Reset))?;
+    }
+    *style = CellStyle::default();
+
+    Ok(())
+}
+
+fn spaced(line: &str) -> String {
+    let mut out = String::with_capacity(line.len() * 2);
+    for c in line.chars() {
+        out.push(c);
+        out.push(' ');
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cell::Cell;
+
+    use super::*;
+    use crate::shapes::{Circle, Sprite, Text};
+
+    fn rect(x: f32, y: f32, width: f32, height: f32) -> Rect {
+        Rect {
+            position: Vector2(x, y),
+            width,
+            height,
+            rotation: 0.0,
+        }
+    }
+
+    /// A 10 by 5 viewport, which covers x from 0 to 10 and y from -5 to 0.
+    fn viewport() -> Renderer<'static> {
+        Renderer::new_headless(10, 5)
+    }
+
+    #[test]
+    fn headless_renderer_renders_to_a_string() {
+        let mut renderer = Renderer::new_headless(4, 3);
+        renderer.add_owned(rect(1.0, -2.0, 2.0, 1.0));
+        renderer.render();
+
+        assert_eq!(renderer.to_string(), "    \n    \n ## ");
+    }
+
+    #[test]
+    fn zoom_is_clamped_to_the_minimum() {
+        let mut renderer = viewport();
+        renderer.add_owned(rect(1.0, -2.0, 3.0, 1.0));
+
+        for zoom in [0.0, -2.0] {
+            renderer.set_zoom(zoom);
+            assert_eq!(renderer.zoom(), MIN_ZOOM);
+            renderer.render();
+        }
+
+        renderer.set_zoom(1.0);
+        renderer.zoom_by(0.0);
+        assert_eq!(renderer.zoom(), MIN_ZOOM);
+        renderer.render();
+    }
+
+    #[test]
+    fn display_and_spaced_strings_match_the_buffer() {
+        let mut renderer = Renderer::new_headless(3, 2);
+        renderer.add_owned(rect(1.0, -1.0, 2.0, 1.0));
+        renderer.render();
+
+        assert_eq!(renderer.to_string(), "   \n ##");
+        assert_eq!(format!("{renderer}"), renderer.to_string());
+        assert_eq!(renderer.to_string_spaced(), "      \n  # # ");
+    }
+
+    #[test]
+    fn render_uses_the_fill_and_background_chars() {
+        let mut renderer = Renderer::new(
+            RendererOptions::builder()
+                .viewport_width(6)
+                .viewport_height(3)
+                .fill_char('o')
+                .background_char('.')
+                .build(),
+        );
+        renderer.add_owned(rect(1.0, -1.0, 3.0, 1.0));
+        renderer.render();
+
+        assert_eq!(renderer.lines(), ["......", ".ooo..", "......"]);
+    }
+
+    #[test]
+    fn builder_overrides_only_the_given_options() {
+        let options = RendererOptions::builder()
+            .viewport_width(12)
+            .fill_char('*')
+            .build();
+
+        assert_eq!((options.viewport_width, options.viewport_height), (12, 24));
+        assert_eq!((options.fill_char, options.background_char), ('*', ' '));
+
+        let options = RendererOptions::builder()
+            .viewport_height(3)
+            .background_char('.')
+            .build();
+
+        assert_eq!((options.viewport_width, options.viewport_height), (80, 3));
+        assert_eq!((options.fill_char, options.background_char), ('#', '.'));
+    }
+
+    #[test]
+    fn finish_frame_waits_for_the_frame_budget() {
+        let mut renderer = Renderer::new(RendererOptions::builder().target_fps(50).build());
+        assert_eq!(renderer.finish_frame(), Duration::ZERO);
+
+        let frame_time = renderer.finish_frame();
+        assert!(frame_time >= Duration::from_millis(20), "{frame_time:?}");
+        assert_eq!(renderer.last_frame_time(), frame_time);
+    }
+
+    #[test]
+    fn walk_with_dt_scales_by_elapsed_time() {
+        let mut renderer = viewport();
+        renderer.walk_with_dt(Vector2::RIGHT, 10.0, Duration::from_millis(250));
+        renderer.walk_with_dt(Vector2::UP, 4.0, Duration::from_secs(2));
+
+        assert!(renderer.position.approx_eq(&Vector2(2.5, 8.0), 1e-6));
+    }
+
+    #[test]
+    fn resize_keeps_drawables_and_camera() {
+        let mut renderer = viewport();
+        renderer.add_owned(rect(4.0, -3.0, 1.0, 1.0));
+        renderer.walk(Vector2(1.0, -1.0), 1.0);
+        renderer.resize(4, 3);
+
+        assert_eq!(renderer.lines(), ["    "; 3]);
+
+        renderer.render();
+        assert_eq!(renderer.lines(), ["    ", "    ", "   #"]);
+    }
+
+    #[test]
+    fn pixels_yield_every_cell_with_its_coordinates() {
+        let mut renderer = viewport();
+        renderer.add_owned(rect(3.0, -2.0, 1.0, 1.0));
+        renderer.render();
+
+        let pixels: Vec<_> = renderer.pixels().collect();
+        assert_eq!(pixels.len(), 50);
+        assert_eq!(pixels[0], (0, 0, ' '));
+        assert_eq!(pixels[49], (9, 4, ' '));
+
+        let filled: Vec<_> = renderer.pixels().filter(|(_, _, c)| *c == '#').collect();
+        assert_eq!(filled, [(3, 2, '#')]);
+    }
+
+    #[test]
+    fn exports_filled_cells_as_ppm_pixels() {
+        let mut renderer = Renderer::new(
+            RendererOptions::builder()
+                .viewport_width(3)
+                .viewport_height(2)
+                .build(),
+        );
+        renderer.add_owned(rect(1.0, -1.0, 1.0, 1.0));
+        renderer.render();
+
+        let mut image = Vec::new();
+        renderer.export_ppm(&mut image, 2).unwrap();
+
+        let header = b"P6\n6 4\n255\n";
+        assert_eq!(&image[..header.len()], header);
+
+        let pixels = &image[header.len()..];
+        assert_eq!(pixels.len(), 6 * 4 * 3);
+        let white: Vec<_> = pixels
+            .chunks(3)
+            .enumerate()
+            .filter(|(_, rgb)| *rgb == [255; 3])
+            .map(|(i, _)| (i % 6, i / 6))
+            .collect();
+        assert_eq!(white, [(2, 2), (3, 2), (2, 3), (3, 3)]);
+    }
+
+    #[test]
+    fn shader_remaps_every_cell_by_position() {
+        let mut renderer = viewport();
+        renderer.add_owned(rect(0.0, -5.0, 10.0, 5.0));
+        renderer.render();
+
+        // Vignette, dimming the outermost ring of cells
+        renderer.apply_shader(|x, y, c| match (x, y) {
+            (0 | 9, _) | (_, 0 | 4) => '.',
+            _ => c,
+        });
+        assert_eq!(
+            renderer.lines(),
+            [
+                "..........",
+                ".########.",
+                ".########.",
+                ".########.",
+                "..........",
+            ]
+        );
+    }
+
+    struct Translucent(Circle, f32);
+
+    impl Drawable for Translucent {
+        fn point_in_self(&self, point: &Vector2<f32>) -> bool {
+            self.0.point_in_self(point)
+        }
+
+        fn bbox(&self) -> Rect {
+            self.0.bbox()
+        }
+
+        fn alpha(&self) -> f32 {
+            self.1
+        }
+    }
+
+    #[test]
+    fn overlapping_translucent_shapes_blend() {
+        let mut renderer = viewport();
+        let circle = |x| Circle {
+            position: Vector2(x, -2.0),
+            radius: 2.0,
+        };
+        renderer.add_owned(Translucent(circle(3.0), 0.5));
+        renderer.add_owned(Translucent(circle(6.0), 0.5));
+        renderer.render();
+
+        assert_eq!(renderer.lines()[2], " +++@@+++ ");
+
+        // An opaque shape on top draws as usual
+        renderer.add_owned(rect(4.0, -2.0, 1.0, 1.0));
+        renderer.render();
+        assert_eq!(renderer.lines()[2], " +++#@+++ ");
+    }
+
+    struct Layered(Rect, f32, char);
+
+    impl Drawable for Layered {
+        fn point_in_self(&self, point: &Vector2<f32>) -> bool {
+            self.0.point_in_self(point)
+        }
+
+        fn bbox(&self) -> Rect {
+            self.0.clone()
+        }
+
+        fn glyph(&self) -> Option<char> {
+            Some(self.2)
+        }
+
+        fn depth(&self) -> f32 {
+            self.1
+        }
+    }
+
+    #[test]
+    fn nearest_depth_wins_each_cell() {
+        let mut renderer = viewport();
+        renderer.add_owned(Layered(rect(1.0, -3.0, 4.0, 3.0), 1.0, 'n'));
+        renderer.add_owned(Layered(rect(3.0, -4.0, 4.0, 3.0), 5.0, 'f'));
+        renderer.add_owned(Layered(rect(6.0, -2.0, 3.0, 1.0), 1.0, 'e'));
+        renderer.render();
+
+        assert_eq!(
+            renderer.lines(),
+            [
+                "          ",
+                " nnnn     ",
+                " nnnnfeee ",
+                " nnnnff   ",
+                "   ffff   ",
+            ]
+        );
+        assert_eq!(renderer.depths[23], 1.0);
+        assert_eq!(renderer.depths[43], 5.0);
+        assert_eq!(renderer.depths[0], f32::INFINITY);
+    }
+
+    #[test]
+    fn translucent_shapes_behind_are_hidden() {
+        let mut renderer = viewport();
+        renderer.add_owned(Translucent(
+            Circle {
+                position: Vector2(4.0, -2.0),
+                radius: 2.0,
+            },
+            0.5,
+        ));
+        renderer.add_owned(Layered(rect(0.0, -5.0, 10.0, 5.0), 1.0, 'b'));
+        renderer.render();
+
+        // The circle is in front, and the wall's alpha doesn't add to it
+        assert_eq!(renderer.lines()[2], "bb+++++bbb");
+    }
+
+    /// A circle with its soft edge, drawn in front of depth 0.
+    struct Near(Circle);
+
+    impl Drawable for Near {
+        fn point_in_self(&self, point: &Vector2<f32>) -> bool {
+            self.0.point_in_self(point)
+        }
+
+        fn bbox(&self) -> Rect {
+            self.0.bbox()
+        }
+
+        fn coverage(&self, point: &Vector2<f32>) -> f32 {
+            self.0.coverage(point)
+        }
+
+        fn depth(&self) -> f32 {
+            -1.0
+        }
+    }
+
+    #[test]
+    fn ramp_mode_draws_the_nearest_shape() {
+        let ramp_viewport = || {
+            Renderer::new(
+                RendererOptions::builder()
+                    .viewport_width(10)
+                    .viewport_height(5)
+                    .ramp(DEFAULT_RAMP)
+                    .build(),
+            )
+        };
+        let near = || {
+            Near(Circle {
+                position: Vector2(4.0, -2.0),
+                radius: 2.0,
+            })
+        };
+        let wall = || Layered(rect(0.0, -5.0, 10.0, 5.0), 0.0, 'w');
+
+        // The half covered edge of the circle shows through the fully
+        // covering wall behind it, whichever was added first
+        let mut renderer = ramp_viewport();
+        renderer.add_owned(near());
+        renderer.add_owned(wall());
+        renderer.render();
+        assert_eq!(renderer.lines()[2], "@@+@@@+@@@");
+
+        let mut renderer = ramp_viewport();
+        renderer.add_owned(wall());
+        renderer.add_owned(near());
+        renderer.render();
+        assert_eq!(renderer.lines()[2], "@@+@@@+@@@");
+    }
+
+    #[test]
+    fn screen_and_world_positions_round_trip() {
+        let mut renderer = viewport();
+        renderer.walk(Vector2(-4.0, 3.0), 1.0);
+        renderer.set_zoom(2.0);
+
+        assert_eq!(renderer.screen_to_world(0, 0), Vector2(-4.0, 3.0));
+        assert_eq!(renderer.screen_to_world(6, 2), Vector2(-1.0, 2.0));
+        assert_eq!(renderer.world_to_screen(&Vector2(-1.0, 2.0)), Some((6, 2)));
+        assert_eq!(renderer.world_to_screen(&Vector2(-0.8, 1.9)), Some((6, 2)));
+        assert_eq!(renderer.world_to_screen(&Vector2(2.0, 0.0)), None);
+        assert_eq!(renderer.world_to_screen(&Vector2(-5.0, 0.0)), None);
+
+        renderer.set_rotation(1.0);
+        for (x, y) in [(0, 0), (3, 1), (9, 4)] {
+            let center = renderer.global_position_of(&Vector2(x as f32 + 0.5, y as f32 + 0.5));
+            assert_eq!(renderer.world_to_screen(&center), Some((x, y)));
+        }
+    }
+
+    #[test]
+    fn pick_returns_the_drawable_in_front() {
+        let mut renderer = viewport();
+        let below = renderer.add_owned(Layered(rect(1.0, -3.0, 4.0, 3.0), 0.0, 'a'));
+        let above = renderer.add_owned(Layered(rect(3.0, -3.0, 4.0, 3.0), 0.0, 'b'));
+        renderer.add_owned(Layered(rect(8.0, -3.0, 2.0, 3.0), 0.0, 'c'));
+        let near = renderer.add_owned(Layered(rect(8.0, -3.0, 2.0, 3.0), -1.0, 'd'));
+
+        assert_eq!(renderer.pick(1, 2), Some(below));
+        assert_eq!(renderer.pick(4, 2), Some(above));
+        assert_eq!(renderer.pick(9, 2), Some(near));
+        assert_eq!(renderer.pick(0, 0), None);
+
+        // Added first, but on top of both
+        let mut renderer = viewport();
+        let raised = renderer.add_owned(Raised(rect(3.0, -3.0, 1.0, 1.0)));
+        renderer.add_owned(rect(1.0, -3.0, 4.0, 3.0));
+        assert_eq!(renderer.pick(3, 3), Some(raised));
+    }
+
+    struct Raised(Rect);
+
+    impl Drawable for Raised {
+        fn point_in_self(&self, point: &Vector2<f32>) -> bool {
+            self.0.point_in_self(point)
+        }
+
+        fn bbox(&self) -> Rect {
+            self.0.clone()
+        }
+
+        fn z(&self) -> i32 {
+            1
+        }
+    }
+
+    struct Stacked(Rect, i32, char);
+
+    impl Drawable for Stacked {
+        fn point_in_self(&self, point: &Vector2<f32>) -> bool {
+            self.0.point_in_self(point)
+        }
+
+        fn bbox(&self) -> Rect {
+            self.0.clone()
+        }
+
+        fn glyph(&self) -> Option<char> {
+            Some(self.2)
+        }
+
+        fn z(&self) -> i32 {
+            self.1
+        }
+    }
+
+    #[test]
+    fn higher_z_wins_then_later_shapes() {
+        let high = || Stacked(rect(2.0, -2.0, 4.0, 1.0), 1, 'h');
+        let left = || Stacked(rect(0.0, -2.0, 8.0, 1.0), 0, 'a');
+        let right = || Stacked(rect(4.0, -2.0, 4.0, 1.0), 0, 'b');
+
+        let mut renderer = viewport();
+        renderer.add_owned(high());
+        renderer.add_owned(left());
+        renderer.add_owned(right());
+        renderer.render();
+        assert_eq!(renderer.lines()[2], "aahhhhbb  ");
+
+        let mut renderer = viewport();
+        renderer.add_owned(right());
+        renderer.add_owned(left());
+        renderer.add_owned(high());
+        renderer.render();
+        assert_eq!(renderer.lines()[2], "aahhhhaa  ");
+    }
+
+    #[test]
+    fn cell_positions_follow_resize() {
+        let mut renderer = viewport();
+        assert_eq!(renderer.local_pixels().len(), 50);
+
+        renderer.resize(4, 3);
+        assert_eq!(renderer.local_pixels().len(), 12);
+        assert_eq!(renderer.local_pixels()[5], Vector2(1.0, 1.0));
+        assert_eq!(renderer.local_pixels().last(), Some(&Vector2(3.0, 2.0)));
+    }
+
+    fn braille_viewport() -> Renderer<'static> {
+        Renderer::new(
+            RendererOptions::builder()
+                .viewport_width(3)
+                .viewport_height(2)
+                .mode(RenderMode::Braille)
+                .build(),
+        )
+    }
+
+    #[test]
+    fn braille_sets_the_covered_dots() {
+        let mut renderer = braille_viewport();
+        // Covers the left column of the first cell below its top row of dots
+        renderer.add_owned(rect(0.0, -0.9, 0.5, 0.9));
+        // Covers every dot of the second cell
+        renderer.add_owned(rect(1.0, -0.9, 1.0, 1.0));
+        renderer.render();
+
+        assert_eq!(renderer.lines(), ["\u{2846}\u{28ff} ", "   "]);
+    }
+
+    #[test]
+    fn braille_ignores_glyphs() {
+        let mut renderer = braille_viewport();
+        renderer.add_owned(Glyph(rect(0.0, -1.9, 3.0, 2.0)));
+        renderer.render();
+
+        assert_eq!(
+            renderer.lines(),
+            ["\u{28ff}".repeat(3), "\u{28ff}".repeat(3)]
+        );
+    }
+
+    #[test]
+    fn half_block_splits_cells_vertically() {
+        let mut renderer = Renderer::new(
+            RendererOptions::builder()
+                .viewport_width(3)
+                .viewport_height(2)
+                .mode(RenderMode::HalfBlock)
+                .build(),
+        );
+        // Covers the top half of the first cell, the bottom half of the
+        // second and all of the third
+        renderer.add_owned(rect(0.0, -0.4, 1.0, 0.8));
+        renderer.add_owned(rect(1.0, -0.9, 1.0, 0.8));
+        renderer.add_owned(Glyph(rect(2.0, -0.9, 1.0, 1.5)));
+        renderer.render();
+
+        assert_eq!(renderer.lines(), ["\u{2580}\u{2584}\u{2588}", "   "]);
+    }
+
+    #[test]
+    fn ramp_follows_coverage() {
+        let mut renderer = Renderer::new(
+            RendererOptions::builder()
+                .viewport_width(7)
+                .viewport_height(1)
+                .ramp(" .o@")
+                .build(),
+        );
+        renderer.add_owned(Circle {
+            position: Vector2(0.0, 0.0),
+            radius: 3.0,
+        });
+        renderer.add_owned(rect(6.0, -0.5, 1.0, 1.0));
+        renderer.render();
+
+        // The circle covers 1, 1, 1, 0.5 then 0, followed by the rect
+        assert_eq!(renderer.lines(), ["@@@o  @"]);
+    }
+
+    #[test]
+    fn supersampling_averages_coverage() {
+        let mut renderer = Renderer::new(
+            RendererOptions::builder()
+                .viewport_width(4)
+                .viewport_height(1)
+                .samples_per_cell(4)
+                .build(),
+        );
+        // Covers all of the first cell, half of the second and a quarter of
+        // the third
+        renderer.add_owned(rect(0.0, -0.9, 1.5, 1.0));
+        renderer.add_owned(rect(2.0, -0.9, 0.25, 1.0));
+        renderer.render();
+
+        assert_eq!(renderer.lines(), ["@+: "]);
+    }
+
+    #[test]
+    fn supersampling_uses_the_given_ramp() {
+        let mut renderer = Renderer::new(
+            RendererOptions::builder()
+                .viewport_width(2)
+                .viewport_height(1)
+                .samples_per_cell(2)
+                .ramp(" ab")
+                .build(),
+        );
+        renderer.add_owned(rect(0.0, -0.9, 1.5, 1.0));
+        renderer.render();
+
+        assert_eq!(renderer.lines(), ["ba"]);
+    }
+
+    /// A rect drawn in a color.
+    struct Colored(Rect, Color);
+
+    impl Drawable for Colored {
+        fn point_in_self(&self, point: &Vector2<f32>) -> bool {
+            self.0.point_in_self(point)
+        }
+
+        fn bbox(&self) -> Rect {
+            self.0.clone()
+        }
+
+        fn color(&self) -> Option<Color> {
+            Some(self.1)
+        }
+    }
+
+    #[test]
+    fn colored_runs_are_printed_together() {
+        let mut renderer = Renderer::new(
+            RendererOptions::builder()
+                .viewport_width(6)
+                .viewport_height(1)
+                .build(),
+        );
+        renderer.add_owned(Colored(rect(0.0, -0.5, 3.0, 1.0), Color::Red));
+        renderer.add_owned(rect(3.0, -0.5, 1.0, 1.0));
+        renderer.add_owned(Colored(rect(4.0, -0.5, 1.0, 1.0), Color::Red));
+        renderer.render();
+
+        let fg: Vec<_> = renderer.styles[..4].iter().map(|style| style.fg).collect();
+        assert_eq!(
+            fg,
+            [Some(Color::Red), Some(Color::Red), Some(Color::Red), None]
+        );
+        let red = "\x1b[38;5;9m";
+        let reset = "\x1b[39m";
+        assert_eq!(
+            draw_output(&mut renderer),
+            format!("\x1b[2J\x1b[1;1H{red}# # # {reset}# {red}# {reset}  \x1b[1E")
+        );
+
+        // Recoloring a cell redraws it even though its character is the same
+        renderer.clear_drawables();
+        renderer.add_owned(Colored(rect(3.0, -0.5, 1.0, 1.0), Color::Blue));
+        renderer.render();
+        assert_eq!(
+            draw_output(&mut renderer),
+            format!("\x1b[1;1H      \x1b[38;5;12m# {reset}  ")
+        );
+    }
+
+    /// A rect drawn on a background color.
+    struct Filled(Rect, Color);
+
+    impl Drawable for Filled {
+        fn point_in_self(&self, point: &Vector2<f32>) -> bool {
+            self.0.point_in_self(point)
+        }
+
+        fn bbox(&self) -> Rect {
+            self.0.clone()
+        }
+
+        fn bg_color(&self) -> Option<Color> {
+            Some(self.1)
+        }
+    }
+
+    #[test]
+    fn background_colors_are_reset_at_the_end_of_each_line() {
+        let mut renderer = Renderer::new(
+            RendererOptions::builder()
+                .viewport_width(3)
+                .viewport_height(2)
+                .build(),
+        );
+        renderer.add_owned(Filled(rect(1.0, -0.5, 2.0, 1.0), Color::Green));
+        renderer.render();
+
+        assert_eq!(
+            draw_output(&mut renderer),
+            "\x1b[2J\x1b[1;1H  \x1b[48;5;10m# # \x1b[49m\x1b[1E      \x1b[1E"
+        );
+    }
+
+    /// A rect drawn with `@`.
+    struct Glyph(Rect);
+
+    impl Drawable for Glyph {
+        fn point_in_self(&self, point: &Vector2<f32>) -> bool {
+            self.0.point_in_self(point)
+        }
+
+        fn bbox(&self) -> Rect {
+            self.0.clone()
+        }
+
+        fn glyph(&self) -> Option<char> {
+            Some('@')
+        }
+    }
+
+    #[test]
+    fn taller_cells_sample_rows_further_apart() {
+        let mut renderer = Renderer::new(
+            RendererOptions::builder()
+                .viewport_width(10)
+                .viewport_height(5)
+                .cell_aspect(4.0)
+                .build(),
+        );
+        let bbox = renderer.bbox();
+
+        assert_eq!((bbox.position.0, bbox.position.1), (0.0, -10.0));
+        assert_eq!((bbox.width, bbox.height), (10.0, 10.0));
+        assert_eq!(
+            renderer.global_position_of(&Vector2(3.0, 2.0)),
+            Vector2(3.0, -4.0)
+        );
+
+        // A circle spans twice as many columns as rows
+        renderer.add_owned(Circle {
+            position: Vector2(5.0, -5.0),
+            radius: 4.0,
+        });
+        renderer.render();
+        assert_eq!(
+            renderer.lines(),
+            [
+                "          ",
+                "   #####  ",
+                "  ####### ",
+                "  ####### ",
+                "   #####  "
+            ]
+        );
+    }
+
+    #[test]
+    fn try_index_rejects_points_outside_the_viewport() {
+        let renderer = viewport();
+
+        assert_eq!(renderer.try_index(&Vector2(0.0, 0.0)), Some(0));
+        assert_eq!(renderer.try_index(&Vector2(9.0, 4.0)), Some(49));
+        assert_eq!(renderer.try_index(&Vector2(9.9, 4.9)), Some(49));
+        assert_eq!(renderer.try_index(&Vector2(10.0, 0.0)), None);
+        assert_eq!(renderer.try_index(&Vector2(0.0, 5.0)), None);
+        assert_eq!(renderer.try_index(&Vector2(-0.5, 0.0)), None);
+        assert_eq!(renderer.try_index(&Vector2(0.0, -0.5)), None);
+    }
+
+    #[test]
+    fn pixels_are_bounds_checked() {
+        let mut renderer = viewport();
+        renderer.set_pixel(9, 4, '*');
+        renderer.set_pixel(10, 0, '*');
+        renderer.set_pixel(0, 5, '*');
+
+        assert_eq!(renderer.get_pixel(9, 4), Some('*'));
+        assert_eq!(renderer.get_pixel(0, 0), Some(' '));
+        assert_eq!(renderer.get_pixel(10, 0), None);
+        assert_eq!(renderer.get_pixel(0, 5), None);
+        assert_eq!(renderer.lines()[4], "         *");
+        assert_eq!(renderer.lines()[0], "          ");
+    }
+
+    #[test]
+    fn draw_line_plots_every_step() {
+        let mut renderer = viewport();
+        renderer.draw_line((0, 0), (9, 3), '*');
+        renderer.draw_line((2, 4), (2, 4), 'o');
+        renderer.draw_line((12, 4), (7, 4), '-');
+
+        assert_eq!(
+            renderer.lines(),
+            [
+                "**        ",
+                "  ***     ",
+                "     ***  ",
+                "        **",
+                "  o    ---"
+            ]
+        );
+    }
+
+    #[test]
+    fn fill_circle_matches_distance_fill() {
+        let options = || {
+            RendererOptions::builder()
+                .viewport_width(20)
+                .viewport_height(12)
+                .build()
+        };
+
+        for (cx, cy) in [(10, 6), (1, 2), (18, 11), (30, 3)] {
+            for radius in 0..8 {
+                let mut spans = Renderer::new(options());
+                spans.fill_circle((cx, cy), radius, '#');
+
+                let mut expected = Renderer::new(options());
+                for y in 0..12 {
+                    for x in 0..20 {
+                        let dx = x as isize - cx as isize;
+                        let dy = y as isize - cy as isize;
+                        if dx * dx + dy * dy <= (radius * radius) as isize {
+                            expected.set_pixel(x, y, '#');
+                        }
+                    }
+                }
+
+                assert_eq!(spans.lines(), expected.lines(), "{cx}, {cy}, {radius}");
+            }
+        }
+    }
+
+    fn draw_output(renderer: &mut Renderer) -> String {
+        let mut out = Vec::new();
+        renderer.draw_to(&mut out).unwrap();
+
+        String::from_utf8(out).unwrap()
+    }
+
+    #[test]
+    fn debug_line_replaces_only_the_drawn_bottom_row() {
+        let mut renderer = Renderer::new(
+            RendererOptions::builder()
+                .viewport_width(34)
+                .viewport_height(3)
+                .show_debug(true)
+                .build(),
+        );
+        renderer.add_owned(rect(0.0, -3.0, 34.0, 3.0));
+        renderer.walk(Vector2(0.0, -0.5), 1.0);
+        renderer.set_zoom(2.0);
+        renderer.render();
+
+        let frame = renderer.frame();
+        let bottom: String = frame.chars[68..].iter().collect();
+        assert_eq!(bottom, "pos=(0.0, -0.5) zoom=2.00 fps=0   ");
+        assert_eq!(frame.chars[..68], renderer.buffer[..68]);
+        assert_eq!(renderer.lines()[2], "#".repeat(34));
+
+        let output = draw_output(&mut renderer);
+        assert!(output.contains("p o s = ( 0 . 0 ,"));
+    }
+
+    #[test]
+    fn axes_and_grid_follow_the_camera() {
+        let mut renderer = Renderer::new(
+            RendererOptions::builder()
+                .viewport_width(10)
+                .viewport_height(5)
+                .show_axes(true)
+                .grid_spacing(2.0)
+                .build(),
+        );
+        renderer.add_owned(rect(5.0, -1.0, 1.0, 1.0));
+        renderer.walk(Vector2(-3.0, 2.0), 1.0);
+        renderer.render();
+
+        assert_eq!(
+            renderer.lines(),
+            [
+                " . | . . .",
+                "   |      ",
+                "---+------",
+                "   |    # ",
+                " . | . . .",
+            ]
+        );
+
+        renderer.set_zoom(0.5);
+        renderer.render();
+        assert_eq!(renderer.lines()[1], "--+-------");
+    }
+
+    #[test]
+    fn text_is_stamped_at_its_position_and_clipped() {
+        let mut renderer = viewport();
+        renderer.add_owned(rect(0.0, -2.0, 10.0, 1.0));
+        renderer.add_owned(Text {
+            position: Vector2(2.0, -2.0),
+            content: "label".to_string(),
+        });
+        renderer.add_owned(Text {
+            position: Vector2(7.0, -4.0),
+            content: "cut off".to_string(),
+        });
+        renderer.add_owned(Text {
+            position: Vector2(-3.0, 0.0),
+            content: "hidden".to_string(),
+        });
+        renderer.render();
+
+        assert_eq!(
+            renderer.lines(),
+            [
+                "den       ",
+                "          ",
+                "##label###",
+                "          ",
+                "       cut"
+            ]
+        );
+
+        // Zooming moves the text with its position but keeps its length
+        renderer.set_zoom(0.5);
+        renderer.render();
+        assert_eq!(renderer.lines()[1], "#label    ");
+    }
+
+    #[test]
+    fn sprites_are_blitted_with_transparent_spaces() {
+        let mut renderer = viewport();
+        renderer.add_owned(rect(0.0, -3.0, 10.0, 1.0));
+        let sprite = renderer.add_owned(Sprite {
+            position: Vector2(1.0, -1.0),
+            rows: vec!["/\\".to_string(), "| |".to_string(), "'-'".to_string()],
+        });
+        renderer.render();
+
+        assert_eq!(
+            renderer.lines(),
+            [
+                "          ",
+                " /\\       ",
+                " | |      ",
+                "#'-'######",
+                "          "
+            ]
+        );
+
+        // Moving the camera moves the sprite, and rows past the edges are cut
+        renderer.walk(Vector2(2.0, -2.0), 1.0);
+        renderer.render();
+        assert_eq!(renderer.lines()[..2], [" |        ", "-'######  "]);
+
+        renderer.get_owned_mut::<Sprite>(sprite).unwrap().position = Vector2(10.0, -4.0);
+        renderer.render();
+        assert_eq!(
+            renderer.lines()[..3],
+            ["          ", "########  ", "        /\\"]
+        );
+    }
+
+    #[test]
+    fn draw_only_writes_changed_cells() {
+        let mut renderer = viewport();
+        renderer.set_pixel(1, 1, '#');
+
+        let first = draw_output(&mut renderer);
+        assert!(first.starts_with("\x1b[2J"));
+        assert!(first.contains("  #   "));
+
+        assert_eq!(draw_output(&mut renderer), "");
+
+        renderer.set_pixel(1, 1, ' ');
+        renderer.set_pixel(2, 1, '@');
+        renderer.set_pixel(3, 1, '@');
+        renderer.set_pixel(9, 4, '%');
+        assert_eq!(draw_output(&mut renderer), "\x1b[2;3H  @ @ \x1b[5;19H% ");
+
+        renderer.redraw_all();
+        assert!(draw_output(&mut renderer).starts_with("\x1b[2J"));
+
+        renderer.resize(4, 2);
+        assert!(draw_output(&mut renderer).starts_with("\x1b[2J"));
+    }
+
+    #[test]
+    fn draw_writes_whole_lines_of_wide_characters() {
+        let mut renderer = braille_viewport();
+        renderer.add_owned(rect(0.0, -1.9, 3.0, 2.0));
+        renderer.render();
+
+        assert!(draw_output(&mut renderer).contains(&"\u{28ff} ".repeat(3)));
+    }
+
+    #[test]
+    fn render_only_writes_inside_the_clip() {
+        let mut renderer = viewport();
+        renderer.set_pixel(0, 0, '*');
+        renderer.set_pixel(4, 2, '*');
+        renderer.add_owned(Circle {
+            position: Vector2(5.0, -2.0),
+            radius: 2.0,
+        });
+        renderer.set_clip(rect(3.0, 1.0, 5.0, 3.0));
+        renderer.render();
+
+        assert_eq!(
+            renderer.lines(),
+            [
+                "*         ",
+                "    ###   ",
+                "   #####  ",
+                "    ###   ",
+                "          "
+            ]
+        );
+
+        renderer.clear_clip();
+        renderer.render();
+        assert_eq!(renderer.lines()[0], "     #    ");
+    }
+
+    #[test]
+    fn bbox_extends_down_from_position() {
+        let bbox = viewport().bbox();
+
+        assert_eq!((bbox.position.0, bbox.position.1), (0.0, -5.0));
+        assert_eq!((bbox.width, bbox.height), (10.0, 5.0));
+    }
+
+    #[test]
+    fn culls_shapes_above_and_below() {
+        let renderer = viewport();
+
+        assert!(!renderer.collides_with_rect(&rect(1.0, 0.5, 2.0, 2.0)));
+        assert!(!renderer.collides_with_rect(&rect(1.0, -8.0, 2.0, 2.0)));
+    }
+
+    #[test]
+    fn keeps_overlapping_shapes() {
+        let renderer = viewport();
+
+        assert!(renderer.collides_with_rect(&rect(1.0, -3.0, 2.0, 2.0)));
+        assert!(renderer.collides_with_rect(&rect(-1.0, -1.0, 2.0, 2.0)));
+        assert!(renderer.collides_with_rect(&rect(-10.0, -10.0, 30.0, 20.0)));
+    }
+
+    #[test]
+    fn shapes_touching_the_sampled_edges_are_kept() {
+        let renderer = viewport();
+
+        // The top row and left column are sampled on the viewport's edges
+        assert!(renderer.collides_with_rect(&rect(1.0, 0.0, 2.0, 2.0)));
+        assert!(renderer.collides_with_rect(&rect(-2.0, -3.0, 2.0, 2.0)));
+
+        // Nothing is sampled on the bottom and right edges
+        assert!(!renderer.collides_with_rect(&rect(1.0, -7.0, 2.0, 2.0)));
+        assert!(!renderer.collides_with_rect(&rect(10.0, -3.0, 2.0, 2.0)));
+    }
+
+    /// A rect that counts how often it is sampled.
+    struct Counting {
+        rect: Rect,
+        tests: Cell<usize>,
+    }
+
+    impl Drawable for Counting {
+        fn point_in_self(&self, point: &Vector2<f32>) -> bool {
+            self.tests.set(self.tests.get() + 1);
+            self.rect.point_in_self(point)
+        }
+
+        fn bbox(&self) -> Rect {
+            self.rect.clone()
+        }
+    }
+
+    #[test]
+    fn off_screen_shapes_are_never_tested() {
+        // Hundreds of squares ringing the viewport on every side
+        let hidden: Vec<_> = (0..500)
+            .map(|i| {
+                let angle = i as f32 * 0.1;
+                let distance = 20.0 + i as f32;
+
+                Counting {
+                    rect: rect(
+                        5.0 + angle.cos() * distance,
+                        -2.5 + angle.sin() * distance,
+                        2.0,
+                        2.0,
+                    ),
+                    tests: Cell::new(0),
+                }
+            })
+            .collect();
+        let shown = Counting {
+            rect: rect(2.0, -3.0, 2.0, 2.0),
+            tests: Cell::new(0),
+        };
+
+        let mut renderer = viewport();
+        renderer.add_drawable(&shown);
+        renderer.render();
+        let alone = renderer.last_stats().pixels_tested;
+
+        for square in hidden.iter() {
+            renderer.add_drawable(square);
+        }
+        renderer.render();
+
+        // The work done is the same as with the visible square alone
+        assert!(hidden.iter().all(|square| square.tests.get() == 0));
+        assert_eq!(renderer.last_stats().shapes_culled, 500);
+        assert_eq!(renderer.last_stats().pixels_tested, alone);
+        assert_eq!(shown.tests.get(), alone * 2);
+    }
+
+    /// `n` by `n` unit squares spread evenly over the default 80 by 24
+    /// viewport.
+    fn spread_squares(n: usize) -> Vec<Counting> {
+        let mut squares = Vec::new();
+        for i in 0..n {
+            for j in 0..n {
+                squares.push(Counting {
+                    rect: rect((i * 80 / n) as f32, -((j * 24 / n) as f32) - 1.0, 1.0, 1.0),
+                    tests: Cell::new(0),
+                });
+            }
+        }
+
+        squares
+    }
+
+    #[test]
+    fn spatial_grid_skips_most_point_tests() {
+        for n in [5, 10, 20] {
+            let squares = spread_squares(n);
+            let mut renderer = Renderer::new(RendererOptions::default());
+            for square in squares.iter() {
+                renderer.add_drawable(square);
+            }
+            renderer.render();
+
+            let tests: usize = squares.iter().map(|square| square.tests.get()).sum();
+            let naive = 80 * 24 * squares.len();
+
+            // Each square is only tested in the few buckets around it, where
+            // the naive loop tests every square at every cell
+            assert!(tests * 10 < naive, "{n}: {tests} of {naive}");
+            assert_eq!(renderer.buffer.iter().filter(|c| **c == '#').count(), n * n);
+        }
+    }
+
+    #[test]
+    fn empty_viewports_render_nothing() {
+        for (width, height) in [(0, 5), (5, 0)] {
+            for mode in [
+                RenderMode::Ascii,
+                RenderMode::Braille,
+                RenderMode::HalfBlock,
+            ] {
+                let mut renderer = Renderer::new(
+                    RendererOptions::builder()
+                        .viewport_width(width)
+                        .viewport_height(height)
+                        .mode(mode)
+                        .show_debug(true)
+                        .show_axes(true)
+                        .build(),
+                );
+                renderer.add_owned(rect(-10.0, -10.0, 20.0, 20.0));
+                renderer.render();
+                renderer.draw_to(&mut Vec::new()).unwrap();
+
+                assert!(renderer.buffer.is_empty());
+            }
+        }
+    }
+
+    #[test]
+    fn render_records_stats() {
+        let squares = spread_squares(5);
+        let far_away = rect(500.0, 500.0, 1.0, 1.0);
+        let mut renderer = Renderer::new(RendererOptions::default());
+        assert_eq!(renderer.last_stats(), RenderStats::default());
+
+        for square in squares.iter() {
+            renderer.add_drawable(square);
+        }
+        renderer.add_drawable(&far_away);
+        renderer.render();
+
+        let stats = renderer.last_stats();
+        let tests: usize = squares.iter().map(|square| square.tests.get()).sum();
+        assert_eq!(stats.shapes_total, 26);
+        assert_eq!(stats.shapes_culled, 1);
+        assert_eq!(stats.pixels_tested, tests);
+    }
+
+    #[test]
+    fn spatial_grid_matches_testing_every_shape() {
+        let squares = spread_squares(12);
+        let mut renderer = Renderer::new(RendererOptions::default());
+        for square in squares.iter() {
+            renderer.add_drawable(square);
+        }
+        renderer.walk(Vector2(10.0, -3.0), 1.0);
+        renderer.set_zoom(1.5);
+        renderer.set_rotation(0.7);
+        renderer.render();
+
+        assert!(renderer.buffer.iter().filter(|c| **c == '#').count() > 20);
+        for (i, c) in renderer.buffer.iter().enumerate() {
+            let cell = Vector2((i % 80) as f32, (i / 80) as f32);
+            let point = renderer.global_position_of(&cell);
+            let inside = squares
+                .iter()
+                .any(|square| square.rect.point_in_self(&point));
+
+            assert_eq!(*c == '#', inside, "{cell}");
+        }
+    }
+
+    #[test]
+    fn shape_touching_the_top_edge_is_drawn() {
+        let circle = Circle {
+            position: Vector2(5.0, 1.0),
+            radius: 1.0,
+        };
+        let mut renderer = viewport();
+        renderer.add_drawable(&circle);
+        renderer.render();
+
+        assert_eq!(renderer.lines()[0], "     #    ");
+    }
+
+    #[test]
+    fn sampled_points_follow_the_camera_rotation() {
+        let mut renderer = viewport();
+        renderer.set_rotation(std::f32::consts::FRAC_PI_2);
+
+        // Unrotated, cell (2, 0) samples (2, 0) and cell (0, 2) samples
+        // (0, -2). Turning the camera a quarter turn counter-clockwise moves
+        // them to (0, 2) and (2, 0)
+        let top = renderer.global_position_of(&Vector2(2.0, 0.0));
+        let left = renderer.global_position_of(&Vector2(0.0, 2.0));
+
+        assert!(top.0.abs() < 1e-5 && (top.1 - 2.0).abs() < 1e-5);
+        assert!((left.0 - 2.0).abs() < 1e-5 && left.1.abs() < 1e-5);
+    }
+
+    #[test]
+    fn rendering_samples_the_rotated_point() {
+        let circle = Circle {
+            position: Vector2(0.0, 2.0),
+            radius: 0.1,
+        };
+        let mut renderer = viewport();
+        renderer.set_rotation(std::f32::consts::FRAC_PI_2);
+        renderer.add_drawable(&circle);
+        renderer.render();
+
+        assert_eq!(
+            renderer.lines(),
+            [
+                "  #       ",
+                "          ",
+                "          ",
+                "          ",
+                "          "
+            ]
+        );
+    }
+
+    #[test]
+    fn owned_shapes_are_drawn() {
+        let mut renderer = viewport();
+        for x in [1.0, 5.0] {
+            renderer.add_owned(rect(x, -1.0, 1.0, 1.0));
+        }
+        renderer.render();
+
+        assert_eq!(renderer.lines()[1], " #   #    ");
+    }
+
+    #[test]
+    fn removed_shapes_are_not_drawn() {
+        let mut renderer = viewport();
+        let left = renderer.add_owned(rect(1.0, -1.0, 1.0, 1.0));
+        let right = renderer.add_owned(rect(5.0, -1.0, 1.0, 1.0));
+
+        assert!(renderer.remove_drawable(left));
+        assert!(!renderer.remove_drawable(left));
+        renderer.render();
+        assert_eq!(renderer.lines()[1], "     #    ");
+
+        renderer.clear_drawables();
+        assert!(!renderer.remove_drawable(right));
+        renderer.render();
+        assert_eq!(renderer.lines()[1], "          ");
+    }
+
+    #[test]
+    fn scene_bounds_cover_every_drawable() {
+        let mut renderer = viewport();
+        assert_eq!(renderer.scene_bounds(), None);
+
+        renderer.add_owned(rect(1.0, -1.0, 1.0, 1.0));
+        renderer.add_owned(Circle {
+            position: Vector2(20.0, 3.0),
+            radius: 2.0,
+        });
+
+        assert_eq!(renderer.scene_bounds(), Some(rect(1.0, -1.0, 21.0, 6.0)));
+    }
+
+    #[test]
+    fn fit_to_scene_frames_the_padded_bounds() {
+        let mut renderer = viewport();
+        renderer.fit_to_scene(2.0);
+        assert_eq!(renderer.bbox(), viewport().bbox());
+
+        renderer.add_owned(rect(0.0, -4.0, 4.0, 2.0));
+        renderer.add_owned(rect(12.0, 0.0, 4.0, 2.0));
+        renderer.fit_to_scene(2.0);
+
+        assert_eq!(renderer.zoom(), 0.5);
+        assert_eq!(renderer.bbox(), rect(-2.0, -6.0, 20.0, 10.0));
+
+        renderer.set_rotation(std::f32::consts::FRAC_PI_2);
+        renderer.fit_to_scene(0.0);
+        let center = renderer.global_position_of(&Vector2(5.0, 2.5));
+
+        assert!((renderer.zoom() - 5.0 / 16.0).abs() < 1e-6);
+        assert!(center.approx_eq(&Vector2(8.0, -1.0), 1e-4));
+    }
+
+    #[test]
+    fn owned_shapes_can_be_moved_by_id() {
+        let mut renderer = viewport();
+        let square = renderer.add_owned(rect(1.0, -1.0, 1.0, 1.0));
+
+        renderer.get_owned_mut::<Rect>(square).unwrap().position.0 = 6.0;
+        renderer.render();
+        assert_eq!(renderer.lines()[1], "      #   ");
+
+        assert!(renderer.get_owned_mut::<Circle>(square).is_none());
+        assert!(renderer.get_drawable_mut(square).is_some());
+        assert_eq!(
+            renderer.get_drawable(square).unwrap().bbox().position.0,
+            6.0
+        );
+    }
+
+    struct Drifting {
+        circle: Circle,
+        velocity: Vector2<f32>,
+    }
+
+    impl Drawable for Drifting {
+        fn point_in_self(&self, point: &Vector2<f32>) -> bool {
+            self.circle.point_in_self(point)
+        }
+
+        fn bbox(&self) -> Rect {
+            self.circle.bbox()
+        }
+    }
+
+    impl Dynamic for Drifting {
+        fn update(&mut self, dt: f32) {
+            self.circle.position += self.velocity.clone() * dt;
+        }
+    }
+
+    #[test]
+    fn update_advances_dynamic_drawables() {
+        let mut renderer = viewport();
+        let still = renderer.add_owned(rect(0.0, 0.0, 1.0, 1.0));
+        let ball = renderer.add_dynamic(Drifting {
+            circle: Circle {
+                position: Vector2(1.0, -2.0),
+                radius: 0.5,
+            },
+            velocity: Vector2(2.0, 0.5),
+        });
+
+        for _ in 0..4 {
+            renderer.update(0.5);
+        }
+
+        let ball = renderer.get_owned_mut::<Drifting>(ball).unwrap();
+        assert!(ball.circle.position.approx_eq(&Vector2(5.0, -1.0), 1e-6));
+        assert_eq!(
+            renderer.get_drawable(still).unwrap().bbox().position,
+            Vector2(0.0, 0.0)
+        );
+    }
+
+    #[test]
+    fn borrowed_shapes_cannot_be_mutated() {
+        let square = rect(1.0, -1.0, 1.0, 1.0);
+        let mut renderer = viewport();
+        let id = renderer.add_drawable(&square);
+
+        assert!(renderer.get_drawable(id).is_some());
+        assert!(renderer.get_drawable_mut(id).is_none());
+        assert!(renderer.get_owned_mut::<Rect>(id).is_none());
+
+        renderer.remove_drawable(id);
+        assert!(renderer.get_drawable(id).is_none());
+    }
+
+    #[test]
+    fn loaded_scene_renders_like_added_shapes() {
+        let shapes = vec![
+            Shape::Rect(rect(1.0, -4.0, 3.0, 2.0)),
+            Shape::Circle(Circle {
+                position: Vector2(7.0, -2.0),
+                radius: 1.5,
+            }),
+        ];
+        let mut loaded = viewport();
+        loaded
+            .load_scene(&scene::to_json(&shapes).unwrap())
+            .unwrap();
+        loaded.render();
+
+        let rect = rect(1.0, -4.0, 3.0, 2.0);
+        let circle = Circle {
+            position: Vector2(7.0, -2.0),
+            radius: 1.5,
+        };
+        let mut added = viewport();
+        added.add_drawable(&rect);
+        added.add_drawable(&circle);
+        added.render();
+
+        assert_eq!(loaded.lines(), added.lines());
+        assert!(loaded.lines().iter().any(|line| line.contains('#')));
+    }
+
+    #[test]
+    fn invalid_scene_adds_nothing() {
+        let mut renderer = viewport();
+        let json = r#"[{"type": "circle", "position": [5, -2], "radius": 2}, {"type": "blob"}]"#;
+
+        assert!(renderer.load_scene(json).is_err());
+        assert!(renderer.drawables.is_empty());
+    }
+
+    #[test]
+    fn global_pixels_line_up_with_the_buffer() {
+        let mut renderer = viewport();
+        renderer.walk(Vector2(3.0, 1.0), 1.0);
+        renderer.set_zoom(2.0);
+        let pixels = renderer.global_pixels();
+
+        assert_eq!(pixels.len(), renderer.buffer.len());
+        assert_eq!((pixels[0].0, pixels[0].1), (3.0, 1.0));
+        assert_eq!((pixels[1].0, pixels[1].1), (3.5, 1.0));
+        assert_eq!((pixels[10].0, pixels[10].1), (3.0, 0.5));
+        for (i, pixel) in pixels.iter().enumerate() {
+            let cell = Vector2((i % 10) as f32, (i / 10) as f32);
+            let expected = renderer.global_position_of(&cell);
+            assert_eq!((pixel.0, pixel.1), (expected.0, expected.1));
+        }
+    }
+}
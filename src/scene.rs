@@ -0,0 +1,731 @@
+use std::{error::Error, fmt, fmt::Write};
+
+use crate::{
+    shapes::{Arc, Circle, Ellipse, Line, Polygon, QuadBezier, Rect, Ring, Shape, Triangle},
+    vector2::Vector2,
+};
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum SceneError {
+    /// The input is not valid JSON. Holds the byte offset where parsing
+    /// failed.
+    Syntax(usize),
+    /// The input is valid JSON but does not describe a scene.
+    Schema(String),
+}
+
+impl fmt::Display for SceneError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SceneError::Syntax(offset) => write!(f, "invalid JSON at byte {offset}"),
+            SceneError::Schema(message) => write!(f, "invalid scene: {message}"),
+        }
+    }
+}
+
+impl Error for SceneError {}
+
+/// Parses a scene, a JSON array of tagged shapes.
+pub fn from_json(json: &str) -> Result<Vec<Shape>, SceneError> {
+    let mut parser = Parser {
+        bytes: json.as_bytes(),
+        pos: 0,
+        depth: 0,
+    };
+    let value = parser.parse_value()?;
+    parser.skip_whitespace();
+    if parser.pos != parser.bytes.len() {
+        return Err(SceneError::Syntax(parser.pos));
+    }
+
+    match value {
+        Json::Array(items) => items.iter().map(shape_from_json).collect(),
+        _ => Err(schema("a scene must be an array of shapes")),
+    }
+}
+
+/// Writes shapes in the format read by `from_json`, one shape per line.
+/// Fails if any number is infinite or NaN, which JSON can't represent.
+pub fn to_json(shapes: &[Shape]) -> Result<String, SceneError> {
+    let mut out = String::from("[");
+
+    for (i, shape) in shapes.iter().enumerate() {
+        out.push_str(if i == 0 { "\n  " } else { ",\n  " });
+
+        // Writing to a String cannot fail
+        let _ = match shape {
+            Shape::Rect(rect) if rect.rotation != 0.0 => write!(
+                out,
+                r#"{{"type": "rect", "position": {}, "width": {}, "height": {}, "rotation": {}}}"#,
+                vector_json(&rect.position)?,
+                finite(rect.width)?,
+                finite(rect.height)?,
+                finite(rect.rotation)?
+            ),
+            Shape::Rect(rect) => write!(
+                out,
+                r#"{{"type": "rect", "position": {}, "width": {}, "height": {}}}"#,
+                vector_json(&rect.position)?,
+                finite(rect.width)?,
+                finite(rect.height)?
+            ),
+            Shape::Circle(circle) => write!(
+                out,
+                r#"{{"type": "circle", "position": {}, "radius": {}}}"#,
+                vector_json(&circle.position)?,
+                finite(circle.radius)?
+            ),
+            Shape::Ring(ring) => write!(
+                out,
+                r#"{{"type": "ring", "position": {}, "inner_radius": {}, "outer_radius": {}}}"#,
+                vector_json(&ring.position)?,
+                finite(ring.inner_radius)?,
+                finite(ring.outer_radius)?
+            ),
+            Shape::Arc(arc) => write!(
+                out,
+                r#"{{"type": "arc", "position": {}, "radius": {}, "start_angle": {}, "end_angle": {}, "thickness": {}}}"#,
+                vector_json(&arc.position)?,
+                finite(arc.radius)?,
+                finite(arc.start_angle)?,
+                finite(arc.end_angle)?,
+                finite(arc.thickness)?
+            ),
+            Shape::Ellipse(ellipse) => write!(
+                out,
+                r#"{{"type": "ellipse", "position": {}, "rx": {}, "ry": {}}}"#,
+                vector_json(&ellipse.position)?,
+                finite(ellipse.rx)?,
+                finite(ellipse.ry)?
+            ),
+            Shape::Line(line) => write!(
+                out,
+                r#"{{"type": "line", "start": {}, "end": {}, "thickness": {}}}"#,
+                vector_json(&line.start)?,
+                vector_json(&line.end)?,
+                finite(line.thickness)?
+            ),
+            Shape::QuadBezier(bezier) => write!(
+                out,
+                r#"{{"type": "quad_bezier", "p0": {}, "p1": {}, "p2": {}, "thickness": {}, "segments": {}}}"#,
+                vector_json(&bezier.p0)?,
+                vector_json(&bezier.p1)?,
+                vector_json(&bezier.p2)?,
+                finite(bezier.thickness)?,
+                bezier.segments
+            ),
+            Shape::Triangle(triangle) => write!(
+                out,
+                r#"{{"type": "triangle", "vertices": {}}}"#,
+                vertices_json(&triangle.vertices)?
+            ),
+            Shape::Polygon(polygon) => write!(
+                out,
+                r#"{{"type": "polygon", "vertices": {}}}"#,
+                vertices_json(&polygon.vertices)?
+            ),
+        };
+    }
+
+    if !shapes.is_empty() {
+        out.push('\n');
+    }
+    out.push(']');
+    Ok(out)
+}
+
+/// `n`, or an error if JSON can't represent it.
+fn finite(n: f32) -> Result<f32, SceneError> {
+    if n.is_finite() {
+        Ok(n)
+    } else {
+        Err(schema(format!("{n} can't be written as JSON")))
+    }
+}
+
+fn vector_json(v: &Vector2<f32>) -> Result<String, SceneError> {
+    Ok(format!("[{}, {}]", finite(v.0)?, finite(v.1)?))
+}
+
+fn vertices_json(vertices: &[Vector2<f32>]) -> Result<String, SceneError> {
+    let vertices = vertices
+        .iter()
+        .map(vector_json)
+        .collect::<Result<Vec<_>, _>>()?;
+
+    Ok(format!("[{}]", vertices.join(", ")))
+}
+
+fn schema(message: impl Into<String>) -> SceneError {
+    SceneError::Schema(message.into())
+}
+
+fn shape_from_json(value: &Json) -> Result<Shape, SceneError> {
+    let Json::Object(fields) = value else {
+        return Err(schema("every shape must be an object"));
+    };
+    let Json::String(kind) = field(fields, "type")? else {
+        return Err(schema("`type` must be a string"));
+    };
+
+    let shape = match kind.as_str() {
+        "rect" => Shape::Rect(Rect {
+            position: vector(field(fields, "position")?)?,
+            width: number_field(fields, "width")?,
+            height: number_field(fields, "height")?,
+            rotation: if fields.iter().any(|(key, _)| key == "rotation") {
+                number_field(fields, "rotation")?
+            } else {
+                0.0
+            },
+        }),
+        "circle" => Shape::Circle(Circle {
+            position: vector(field(fields, "position")?)?,
+            radius: number_field(fields, "radius")?,
+        }),
+        "ring" => Shape::Ring(Ring {
+            position: vector(field(fields, "position")?)?,
+            inner_radius: number_field(fields, "inner_radius")?,
+            outer_radius: number_field(fields, "outer_radius")?,
+        }),
+        "arc" => Shape::Arc(Arc {
+            position: vector(field(fields, "position")?)?,
+            radius: number_field(fields, "radius")?,
+            start_angle: number_field(fields, "start_angle")?,
+            end_angle: number_field(fields, "end_angle")?,
+            thickness: number_field(fields, "thickness")?,
+        }),
+        "ellipse" => Shape::Ellipse(Ellipse {
+            position: vector(field(fields, "position")?)?,
+            rx: number_field(fields, "rx")?,
+            ry: number_field(fields, "ry")?,
+        }),
+        "line" => Shape::Line(Line {
+            start: vector(field(fields, "start")?)?,
+            end: vector(field(fields, "end")?)?,
+            thickness: number_field(fields, "thickness")?,
+        }),
+        "quad_bezier" => Shape::QuadBezier(QuadBezier {
+            p0: vector(field(fields, "p0")?)?,
+            p1: vector(field(fields, "p1")?)?,
+            p2: vector(field(fields, "p2")?)?,
+            thickness: number_field(fields, "thickness")?,
+            segments: count_field(fields, "segments")?,
+        }),
+        "triangle" => {
+            let vertices = vertices(field(fields, "vertices")?)?;
+            let vertices = vertices
+                .try_into()
+                .map_err(|_| schema("a triangle needs exactly 3 vertices"))?;
+
+            Shape::Triangle(Triangle { vertices })
+        }
+        "polygon" => Shape::Polygon(Polygon {
+            vertices: vertices(field(fields, "vertices")?)?,
+        }),
+        other => return Err(schema(format!("unknown shape type `{other}`"))),
+    };
+
+    Ok(shape)
+}
+
+fn field<'j>(fields: &'j [(String, Json)], name: &str) -> Result<&'j Json, SceneError> {
+    fields
+        .iter()
+        .find(|(key, _)| key == name)
+        .map(|(_, value)| value)
+        .ok_or_else(|| schema(format!("missing field `{name}`")))
+}
+
+fn number_field(fields: &[(String, Json)], name: &str) -> Result<f32, SceneError> {
+    match field(fields, name)? {
+        Json::Number(n) => Ok(*n),
+        _ => Err(schema(format!("`{name}` must be a number"))),
+    }
+}
+
+fn count_field(fields: &[(String, Json)], name: &str) -> Result<usize, SceneError> {
+    match number_field(fields, name)? {
+        n if n >= 0.0 && n.fract() == 0.0 => Ok(n as usize),
+        _ => Err(schema(format!("`{name}` must be a whole number"))),
+    }
+}
+
+fn vector(value: &Json) -> Result<Vector2<f32>, SceneError> {
+    match value {
+        Json::Array(items) => match items.as_slice() {
+            [Json::Number(x), Json::Number(y)] => Ok(Vector2(*x, *y)),
+            _ => Err(schema("a vector must be an array of two numbers")),
+        },
+        _ => Err(schema("a vector must be an array of two numbers")),
+    }
+}
+
+fn vertices(value: &Json) -> Result<Vec<Vector2<f32>>, SceneError> {
+    match value {
+        Json::Array(items) => items.iter().map(vector).collect(),
+        _ => Err(schema("`vertices` must be an array of vectors")),
+    }
+}
+
+/// Whether `bytes` follow the JSON number grammar, which is stricter than
+/// `f32::from_str`: no leading `+`, no leading zeros, and digits on both sides
+/// of the decimal point.
+fn is_json_number(bytes: &[u8]) -> bool {
+    let digits = |bytes: &[u8]| bytes.iter().take_while(|b| b.is_ascii_digit()).count();
+
+    let mut rest = bytes.strip_prefix(b"-").unwrap_or(bytes);
+    match digits(rest) {
+        0 => return false,
+        n if n > 1 && rest[0] == b'0' => return false,
+        n => rest = &rest[n..],
+    }
+    if let Some(fraction) = rest.strip_prefix(b".") {
+        match digits(fraction) {
+            0 => return false,
+            n => rest = &fraction[n..],
+        }
+    }
+    if let Some(exponent) = rest.strip_prefix(b"e").or_else(|| rest.strip_prefix(b"E")) {
+        let exponent = exponent
+            .strip_prefix(b"+")
+            .or_else(|| exponent.strip_prefix(b"-"))
+            .unwrap_or(exponent);
+        match digits(exponent) {
+            0 => return false,
+            n => rest = &exponent[n..],
+        }
+    }
+
+    rest.is_empty()
+}
+
+/// The subset of JSON values needed for scene files.
+enum Json {
+    /// `true`, `false` or `null`, which scenes never use.
+    Literal,
+    Number(f32),
+    String(String),
+    Array(Vec<Json>),
+    Object(Vec<(String, Json)>),
+}
+
+/// How deeply arrays and objects may nest before parsing gives up, so that
+/// hostile input can't overflow the stack.
+const MAX_DEPTH: usize = 128;
+
+struct Parser<'s> {
+    bytes: &'s [u8],
+    pos: usize,
+    /// Arrays and objects currently being parsed.
+    depth: usize,
+}
+
+impl Parser<'_> {
+    fn error<T>(&self) -> Result<T, SceneError> {
+        Err(SceneError::Syntax(self.pos))
+    }
+
+    fn skip_whitespace(&mut self) {
+        while let Some(b' ' | b'\t' | b'\n' | b'\r') = self.bytes.get(self.pos) {
+            self.pos += 1;
+        }
+    }
+
+    fn peek(&mut self) -> Option<u8> {
+        self.skip_whitespace();
+        self.bytes.get(self.pos).copied()
+    }
+
+    fn expect(&mut self, byte: u8) -> Result<(), SceneError> {
+        if self.peek() == Some(byte) {
+            self.pos += 1;
+            Ok(())
+        } else {
+            self.error()
+        }
+    }
+
+    fn parse_value(&mut self) -> Result<Json, SceneError> {
+        match self.peek() {
+            Some(b'[') => self.parse_nested(Self::parse_array),
+            Some(b'{') => self.parse_nested(Self::parse_object),
+            Some(b'"') => Ok(Json::String(self.parse_string()?)),
+            Some(b'-' | b'0'..=b'9') => self.parse_number(),
+            Some(b't') => self.parse_literal("true"),
+            Some(b'f') => self.parse_literal("false"),
+            Some(b'n') => self.parse_literal("null"),
+            _ => self.error(),
+        }
+    }
+
+    fn parse_nested(
+        &mut self,
+        parse: fn(&mut Self) -> Result<Json, SceneError>,
+    ) -> Result<Json, SceneError> {
+        if self.depth == MAX_DEPTH {
+            return self.error();
+        }
+
+        self.depth += 1;
+        let value = parse(self);
+        self.depth -= 1;
+        value
+    }
+
+    fn parse_literal(&mut self, literal: &str) -> Result<Json, SceneError> {
+        if self.bytes[self.pos..].starts_with(literal.as_bytes()) {
+            self.pos += literal.len();
+            Ok(Json::Literal)
+        } else {
+            self.error()
+        }
+    }
+
+    fn parse_number(&mut self) -> Result<Json, SceneError> {
+        let start = self.pos;
+        while let Some(b'-' | b'+' | b'.' | b'e' | b'E' | b'0'..=b'9') = self.bytes.get(self.pos) {
+            self.pos += 1;
+        }
+
+        let bytes = &self.bytes[start..self.pos];
+        if !is_json_number(bytes) {
+            return Err(SceneError::Syntax(start));
+        }
+
+        // The scanned bytes are all ASCII, so this slice is valid UTF-8
+        let text = std::str::from_utf8(bytes).unwrap_or_default();
+        match text.parse::<f32>() {
+            // Too large for an `f32`, which `to_json` couldn't write back
+            Ok(n) if n.is_finite() => Ok(Json::Number(n)),
+            _ => Err(SceneError::Syntax(start)),
+        }
+    }
+
+    fn parse_string(&mut self) -> Result<String, SceneError> {
+        self.expect(b'"')?;
+        let mut out = Vec::new();
+
+        loop {
+            let Some(&byte) = self.bytes.get(self.pos) else {
+                return self.error();
+            };
+            self.pos += 1;
+
+            match byte {
+                b'"' => break,
+                // Control characters must be escaped
+                0..0x20 => {
+                    self.pos -= 1;
+                    return self.error();
+                }
+                b'\\' => {
+                    let escaped = match self.bytes.get(self.pos) {
+                        Some(b'"') => '"',
+                        Some(b'\\') => '\\',
+                        Some(b'/') => '/',
+                        Some(b'n') => '\n',
+                        Some(b't') => '\t',
+                        Some(b'r') => '\r',
+                        Some(b'b') => '\u{8}',
+                        Some(b'f') => '\u{c}',
+                        Some(b'u') => self.parse_unicode_escape()?,
+                        _ => return self.error(),
+                    };
+                    self.pos += 1;
+                    out.extend_from_slice(escaped.encode_utf8(&mut [0; 4]).as_bytes());
+                }
+                _ => out.push(byte),
+            }
+        }
+
+        // Only whole characters were copied from the input, so this is valid
+        // UTF-8
+        String::from_utf8(out).or_else(|_| self.error())
+    }
+
+    /// Reads the `uXXXX` of a `\u` escape starting at the `u`, along with
+    /// the low half of a surrogate pair after it. Leaves `pos` on the last
+    /// hex digit read.
+    fn parse_unicode_escape(&mut self) -> Result<char, SceneError> {
+        let high = self.hex_digits(self.pos + 1)?;
+        self.pos += 4;
+
+        let code = if (0xD800..0xDC00).contains(&high) {
+            // A high surrogate must be followed by `\u` and a low surrogate
+            if self.bytes.get(self.pos + 1..self.pos + 3) != Some(b"\\u") {
+                return self.error();
+            }
+            let low = self.hex_digits(self.pos + 3)?;
+            if !(0xDC00..0xE000).contains(&low) {
+                return self.error();
+            }
+            self.pos += 6;
+
+            0x10000 + ((high - 0xD800) << 10) + (low - 0xDC00)
+        } else {
+            high
+        };
+
+        // Lone low surrogates are not characters
+        char::from_u32(code).map_or_else(|| self.error(), Ok)
+    }
+
+    /// The four hex digits starting at `at`.
+    fn hex_digits(&self, at: usize) -> Result<u32, SceneError> {
+        match self.bytes.get(at..at + 4) {
+            Some(hex) if hex.iter().all(u8::is_ascii_hexdigit) => {
+                // Hex digits are ASCII, so this is valid UTF-8
+                let hex = std::str::from_utf8(hex).unwrap_or_default();
+                u32::from_str_radix(hex, 16).or_else(|_| self.error())
+            }
+            _ => self.error(),
+        }
+    }
+
+    fn parse_array(&mut self) -> Result<Json, SceneError> {
+        self.expect(b'[')?;
+        let mut items = Vec::new();
+
+        if self.peek() == Some(b']') {
+            self.pos += 1;
+            return Ok(Json::Array(items));
+        }
+
+        loop {
+            items.push(self.parse_value()?);
+            match self.peek() {
+                Some(b',') => self.pos += 1,
+                Some(b']') => {
+                    self.pos += 1;
+                    return Ok(Json::Array(items));
+                }
+                _ => return self.error(),
+            }
+        }
+    }
+
+    fn parse_object(&mut self) -> Result<Json, SceneError> {
+        self.expect(b'{')?;
+        let mut fields = Vec::new();
+
+        if self.peek() == Some(b'}') {
+            self.pos += 1;
+            return Ok(Json::Object(fields));
+        }
+
+        loop {
+            self.skip_whitespace();
+            let key = self.parse_string()?;
+            self.expect(b':')?;
+            fields.push((key, self.parse_value()?));
+            match self.peek() {
+                Some(b',') => self.pos += 1,
+                Some(b'}') => {
+                    self.pos += 1;
+                    return Ok(Json::Object(fields));
+                }
+                _ => return self.error(),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn scene() -> Vec<Shape> {
+        vec![
+            Shape::Rect(Rect {
+                position: Vector2(-1.5, 2.0),
+                width: 10.0,
+                height: 0.25,
+                rotation: 0.0,
+            }),
+            Shape::Rect(Rect {
+                position: Vector2(4.0, 4.0),
+                width: 2.0,
+                height: 1.0,
+                rotation: 0.5,
+            }),
+            Shape::Circle(Circle {
+                position: Vector2(0.1, -3.0),
+                radius: 4.0,
+            }),
+            Shape::Ring(Ring {
+                position: Vector2(-2.0, 1.0),
+                inner_radius: 1.5,
+                outer_radius: 2.5,
+            }),
+            Shape::Arc(Arc {
+                position: Vector2(0.0, 0.0),
+                radius: 3.0,
+                start_angle: -0.5,
+                end_angle: 2.0,
+                thickness: 1.0,
+            }),
+            Shape::Ellipse(Ellipse {
+                position: Vector2(0.0, 0.0),
+                rx: 3.0,
+                ry: 1.5,
+            }),
+            Shape::QuadBezier(QuadBezier {
+                p0: Vector2(0.0, 0.0),
+                p1: Vector2(2.0, 4.0),
+                p2: Vector2(4.0, 0.0),
+                thickness: 0.5,
+                segments: 12,
+            }),
+            Shape::Line(Line {
+                start: Vector2(0.0, 0.0),
+                end: Vector2(5.0, 5.0),
+                thickness: 1.0,
+            }),
+            Shape::Triangle(Triangle {
+                vertices: [Vector2(0.0, 0.0), Vector2(4.0, 0.0), Vector2(0.0, 3.0)],
+            }),
+            Shape::Polygon(Polygon {
+                vertices: vec![
+                    Vector2(0.0, 0.0),
+                    Vector2(2.0, 0.0),
+                    Vector2(2.0, 2.0),
+                    Vector2(0.0, 2.0),
+                ],
+            }),
+        ]
+    }
+
+    #[test]
+    fn round_trips_every_shape() {
+        let shapes = scene();
+
+        assert_eq!(from_json(&to_json(&shapes).unwrap()), Ok(shapes));
+        assert_eq!(from_json(&to_json(&[]).unwrap()), Ok(vec![]));
+    }
+
+    #[test]
+    fn refuses_to_write_non_finite_numbers() {
+        let circle = |x: f32, radius: f32| {
+            Shape::Circle(Circle {
+                position: Vector2(x, 0.0),
+                radius,
+            })
+        };
+
+        for shape in [circle(f32::NAN, 1.0), circle(0.0, f32::INFINITY)] {
+            assert!(matches!(to_json(&[shape]), Err(SceneError::Schema(_))));
+        }
+    }
+
+    #[test]
+    fn parses_handwritten_scene() {
+        let json = r#"
+            [
+                { "type": "circle", "position": [1, -2.5e1], "radius": 3, "name": "suné", "hidden": false },
+                { "radius": 1, "type": "circle", "position": [0, 0] }
+            ]
+        "#;
+
+        assert_eq!(
+            from_json(json),
+            Ok(vec![
+                Shape::Circle(Circle {
+                    position: Vector2(1.0, -25.0),
+                    radius: 3.0,
+                }),
+                Shape::Circle(Circle {
+                    position: Vector2(0.0, 0.0),
+                    radius: 1.0,
+                }),
+            ])
+        );
+    }
+
+    #[test]
+    fn parses_unicode_escapes() {
+        let text = |json: &str| {
+            let mut parser = Parser {
+                bytes: json.as_bytes(),
+                pos: 0,
+                depth: 0,
+            };
+            parser.parse_string()
+        };
+
+        assert_eq!(text(r#""caf\u00e9""#), Ok("café".to_string()));
+        assert_eq!(text(r#""\ud83d\ude00!""#), Ok("😀!".to_string()));
+        assert!(text(r#""\ud83d""#).is_err());
+        assert!(text(r#""\ud83d\u0041""#).is_err());
+        assert!(text(r#""\ude00""#).is_err());
+        assert!(text(r#""\u+0e9""#).is_err());
+    }
+
+    #[test]
+    fn rejects_malformed_json() {
+        assert_eq!(from_json("[{]"), Err(SceneError::Syntax(2)));
+        assert_eq!(from_json("[] x"), Err(SceneError::Syntax(3)));
+        assert!(matches!(from_json(r#"["abc"#), Err(SceneError::Syntax(_))));
+    }
+
+    #[test]
+    fn numbers_follow_the_json_grammar() {
+        let number = |json: &str| match from_json(&format!("[{json}]")) {
+            Err(SceneError::Syntax(_)) => None,
+            _ => Some(()),
+        };
+
+        for valid in ["0", "-0", "10", "0.5", "-1.25e3", "2E-2", "1e+2", "3.4e38"] {
+            assert!(number(valid).is_some(), "{valid}");
+        }
+        for invalid in [
+            "01", "-01", "+1", ".5", "1.", "1e", "1e+", "--1", "1e99", "-1e99",
+        ] {
+            assert!(number(invalid).is_none(), "{invalid}");
+        }
+    }
+
+    #[test]
+    fn rejects_raw_control_characters_in_strings() {
+        assert_eq!(from_json("[\"a\tb\"]"), Err(SceneError::Syntax(3)));
+        assert_eq!(from_json("[\"a\nb\"]"), Err(SceneError::Syntax(3)));
+        let escaped = r#"[{"type": "circle", "position": [0, 0], "radius": 1, "name": "a\tb"}]"#;
+        assert!(from_json(escaped).is_ok());
+    }
+
+    #[test]
+    fn rejects_deeply_nested_json() {
+        let nested = |depth| {
+            format!(
+                r#"[{{"type": "circle", "position": [0, 0], "radius": 1, "extra": {}{}}}]"#,
+                "[".repeat(depth),
+                "]".repeat(depth)
+            )
+        };
+
+        assert!(from_json(&nested(100)).is_ok());
+        assert!(matches!(
+            from_json(&nested(100_000)),
+            Err(SceneError::Syntax(_))
+        ));
+    }
+
+    #[test]
+    fn rejects_invalid_shapes() {
+        let errors = [
+            r#"{"type": "circle"}"#,
+            r#"[{"type": "hexagon"}]"#,
+            r#"[{"type": "circle", "radius": 1}]"#,
+            r#"[{"type": "circle", "position": [1], "radius": 1}]"#,
+            r#"[{"type": "triangle", "vertices": [[0, 0], [1, 1]]}]"#,
+            r#"[{"type": "quad_bezier", "p0": [0, 0], "p1": [1, 1], "p2": [2, 0], "thickness": 1, "segments": 2.5}]"#,
+        ];
+
+        for json in errors {
+            assert!(
+                matches!(from_json(json), Err(SceneError::Schema(_))),
+                "{json}"
+            );
+        }
+    }
+}
@@ -0,0 +1,1582 @@
+use std::f32::consts::TAU;
+
+use crossterm::style::Color;
+
+use crate::vector2::Vector2;
+
+pub trait Drawable {
+    fn point_in_self(&self, point: &Vector2<f32>) -> bool;
+    fn bbox(&self) -> Rect;
+
+    /// Geometric center of the shape, for rotating or grouping it. Defaults
+    /// to the center of `bbox`.
+    fn center(&self) -> Vector2<f32> {
+        let bbox = self.bbox();
+
+        bbox.position.clone() + Vector2(bbox.width, bbox.height) / 2.0
+    }
+
+    /// Character used for pixels inside the shape. `None` falls back to the
+    /// renderer's `fill_char`.
+    fn glyph(&self) -> Option<char> {
+        None
+    }
+
+    /// Foreground color used for pixels inside the shape. `None` uses the
+    /// terminal's default color.
+    fn color(&self) -> Option<Color> {
+        None
+    }
+
+    /// Background color of the cells the shape is drawn in. `None` uses the
+    /// terminal's default background.
+    fn bg_color(&self) -> Option<Color> {
+        None
+    }
+
+    /// Opacity of the shape from `0.0` to `1.0`. Where the highest shape
+    /// covering a cell is below `1.0`, `Ascii` mode draws the cell with a
+    /// denser character the more alpha the shapes there add up to, so
+    /// overlaps stand out. Defaults to `1.0`, drawn as usual.
+    fn alpha(&self) -> f32 {
+        1.0
+    }
+
+    /// Distance of the shape from the viewer, resolved per cell. Where
+    /// shapes overlap, the lowest depth is drawn in front regardless of `z`,
+    /// and equal depths fall back to the `z` order. Defaults to `0.0`.
+    fn depth(&self) -> f32 {
+        0.0
+    }
+
+    /// Stacking order of the shape. Higher values are drawn on top, shapes
+    /// with equal values are drawn in the order they were added.
+    fn z(&self) -> i32 {
+        0
+    }
+
+    /// Text written into the buffer character by character, starting at the
+    /// cell showing the given world position. Shapes returning `Some` are
+    /// not sampled with `point_in_self`. Defaults to `None`.
+    fn text(&self) -> Option<(&Vector2<f32>, &str)> {
+        None
+    }
+
+    /// Rows of characters drawn like `text`, one row per line below the
+    /// first. Spaces are transparent. Defaults to `None`.
+    fn sprite(&self) -> Option<(&Vector2<f32>, &[String])> {
+        None
+    }
+
+    /// How much of the shape covers a point, from `0.0` (outside) to `1.0`
+    /// (inside). Used with `RendererOptions::ramp` to soften edges. Defaults
+    /// to `point_in_self`, so edges are hard.
+    fn coverage(&self, point: &Vector2<f32>) -> f32 {
+        if self.point_in_self(point) {
+            1.0
+        } else {
+            0.0
+        }
+    }
+
+    /// Whether a point is inside the shape and within `width` of its
+    /// boundary. Used by `Stroke` to draw outlines. The default implementation
+    /// samples the eight neighbouring points `width` away, shapes with a
+    /// cheaper exact test should override it.
+    fn point_on_outline(&self, point: &Vector2<f32>, width: f32) -> bool {
+        if !self.point_in_self(point) {
+            return false;
+        }
+
+        let diagonal = width * std::f32::consts::FRAC_1_SQRT_2;
+        let offsets = [
+            Vector2(width, 0.0),
+            Vector2(-width, 0.0),
+            Vector2(0.0, width),
+            Vector2(0.0, -width),
+            Vector2(diagonal, diagonal),
+            Vector2(diagonal, -diagonal),
+            Vector2(-diagonal, diagonal),
+            Vector2(-diagonal, -diagonal),
+        ];
+
+        offsets
+            .into_iter()
+            .any(|offset| !self.point_in_self(&(point.clone() + offset)))
+    }
+}
+
+/// Drawables that change on their own over time, such as a shape moving
+/// with a velocity. Added to a renderer with `Renderer::add_dynamic`.
+pub trait Dynamic: Drawable {
+    /// Advances the drawable by `dt` seconds. Does nothing by default.
+    fn update(&mut self, _dt: f32) {}
+}
+
+/// Draws only the outline of the wrapped shape, `width` units thick.
+#[derive(Debug, Clone)]
+pub struct Stroke<T: Drawable> {
+    pub shape: T,
+    pub width: f32,
+}
+
+impl<T: Drawable> Drawable for Stroke<T> {
+    fn point_in_self(&self, point: &Vector2<f32>) -> bool {
+        self.shape.point_on_outline(point, self.width)
+    }
+
+    fn bbox(&self) -> Rect {
+        self.shape.bbox()
+    }
+
+    fn center(&self) -> Vector2<f32> {
+        self.shape.center()
+    }
+
+    fn glyph(&self) -> Option<char> {
+        self.shape.glyph()
+    }
+
+    fn color(&self) -> Option<Color> {
+        self.shape.color()
+    }
+
+    fn bg_color(&self) -> Option<Color> {
+        self.shape.bg_color()
+    }
+
+    fn alpha(&self) -> f32 {
+        self.shape.alpha()
+    }
+
+    fn depth(&self) -> f32 {
+        self.shape.depth()
+    }
+
+    fn z(&self) -> i32 {
+        self.shape.z()
+    }
+}
+
+/// Draws `inner` scaled by `scale` around the origin, then rotated
+/// counter-clockwise by `rotate` radians around the origin and finally moved
+/// by `translate`. Works with any drawable, a scaled `Circle` becomes an
+/// ellipse. Both components of `scale` must be non-zero.
+#[derive(Debug, Clone)]
+pub struct Transformed<T: Drawable> {
+    pub inner: T,
+    pub translate: Vector2<f32>,
+    pub rotate: f32,
+    pub scale: Vector2<f32>,
+}
+
+impl<T: Drawable> Transformed<T> {
+    /// Wraps `inner` without changing it, ready for the fields to be set.
+    pub fn new(inner: T) -> Self {
+        Self {
+            inner,
+            translate: Vector2::ZERO,
+            rotate: 0.0,
+            scale: Vector2(1.0, 1.0),
+        }
+    }
+
+    /// Where a point of `inner` ends up.
+    pub fn apply(&self, point: &Vector2<f32>) -> Vector2<f32> {
+        let scaled = Vector2(point.0 * self.scale.0, point.1 * self.scale.1);
+
+        scaled.rotate(self.rotate) + self.translate.clone()
+    }
+
+    /// The point of `inner` that ends up at `point`, the inverse of
+    /// `apply`.
+    pub fn invert(&self, point: &Vector2<f32>) -> Vector2<f32> {
+        let unrotated = (point.clone() - self.translate.clone()).rotate(-self.rotate);
+
+        Vector2(unrotated.0 / self.scale.0, unrotated.1 / self.scale.1)
+    }
+}
+
+impl<T: Drawable> Drawable for Transformed<T> {
+    fn point_in_self(&self, point: &Vector2<f32>) -> bool {
+        self.inner.point_in_self(&self.invert(point))
+    }
+
+    /// The box around the transformed corners of the inner bbox.
+    fn bbox(&self) -> Rect {
+        let bbox = self.inner.bbox();
+        let corners = [
+            Vector2(bbox.position.0, bbox.position.1),
+            Vector2(bbox.position.0 + bbox.width, bbox.position.1),
+            Vector2(bbox.position.0, bbox.position.1 + bbox.height),
+            Vector2(bbox.position.0 + bbox.width, bbox.position.1 + bbox.height),
+        ]
+        .map(|corner| self.apply(&corner));
+
+        bbox_of(&corners)
+    }
+
+    fn center(&self) -> Vector2<f32> {
+        self.apply(&self.inner.center())
+    }
+
+    fn coverage(&self, point: &Vector2<f32>) -> f32 {
+        self.inner.coverage(&self.invert(point))
+    }
+
+    fn glyph(&self) -> Option<char> {
+        self.inner.glyph()
+    }
+
+    fn color(&self) -> Option<Color> {
+        self.inner.color()
+    }
+
+    fn bg_color(&self) -> Option<Color> {
+        self.inner.bg_color()
+    }
+
+    fn alpha(&self) -> f32 {
+        self.inner.alpha()
+    }
+
+    fn depth(&self) -> f32 {
+        self.inner.depth()
+    }
+
+    fn z(&self) -> i32 {
+        self.inner.z()
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Rect {
+    pub position: Vector2<f32>,
+    pub width: f32,
+    pub height: f32,
+    /// Counter-clockwise tilt in radians around the center of the
+    /// rectangle. Followed by drawing and `contains_point`. The box methods
+    /// `contains_rect`, `intersection` and `union` only accept untilted
+    /// rectangles.
+    pub rotation: f32,
+}
+
+impl Default for Rect {
+    fn default() -> Self {
+        Self::new(Vector2::ZERO, 0.0, 0.0)
+    }
+}
+
+impl Rect {
+    /// An untilted rectangle with its bottom left corner at `position`.
+    pub fn new(position: Vector2<f32>, width: f32, height: f32) -> Self {
+        Self {
+            position,
+            width,
+            height,
+            rotation: 0.0,
+        }
+    }
+
+    /// Rotates a point into the frame where the rectangle is not tilted.
+    fn untilted(&self, point: &Vector2<f32>) -> Vector2<f32> {
+        if self.rotation == 0.0 {
+            return point.clone();
+        }
+
+        point.rotate_around(&self.center(), -self.rotation)
+    }
+
+    /// Whether the point lies inside the rectangle, following its rotation.
+    /// The left and bottom edges are inclusive, the right and top edges
+    /// exclusive.
+    pub fn contains_point(&self, point: &Vector2<f32>) -> bool {
+        let point = self.untilted(point);
+        let max_x = self.position.0 + self.width;
+        let max_y = self.position.1 + self.height;
+
+        (self.position.0..max_x).contains(&point.0) && (self.position.1..max_y).contains(&point.1)
+    }
+
+    /// Whether `other` lies entirely within the rectangle, edges included.
+    /// Both rectangles must be untilted.
+    pub fn contains_rect(&self, other: &Rect) -> bool {
+        debug_assert!(self.rotation == 0.0 && other.rotation == 0.0);
+
+        other.position.0 >= self.position.0
+            && other.position.1 >= self.position.1
+            && other.position.0 + other.width <= self.position.0 + self.width
+            && other.position.1 + other.height <= self.position.1 + self.height
+    }
+
+    /// The overlapping region of both rectangles, or `None` if they are
+    /// disjoint. Rectangles that only touch along an edge or corner produce a
+    /// zero-area rectangle on the shared boundary. Both rectangles must be
+    /// untilted.
+    pub fn intersection(&self, other: &Rect) -> Option<Rect> {
+        debug_assert!(self.rotation == 0.0 && other.rotation == 0.0);
+
+        let left = self.position.0.max(other.position.0);
+        let bottom = self.position.1.max(other.position.1);
+        let right = (self.position.0 + self.width).min(other.position.0 + other.width);
+        let top = (self.position.1 + self.height).min(other.position.1 + other.height);
+
+        if left > right || bottom > top {
+            return None;
+        }
+
+        Some(Rect {
+            position: Vector2(left, bottom),
+            width: right - left,
+            height: top - bottom,
+            rotation: 0.0,
+        })
+    }
+
+    /// The smallest rectangle containing both rectangles. Zero-area
+    /// rectangles still extend the result to include their position. Both
+    /// rectangles must be untilted, use their `bbox` otherwise.
+    pub fn union(&self, other: &Rect) -> Rect {
+        debug_assert!(self.rotation == 0.0 && other.rotation == 0.0);
+
+        let left = self.position.0.min(other.position.0);
+        let bottom = self.position.1.min(other.position.1);
+        let right = (self.position.0 + self.width).max(other.position.0 + other.width);
+        let top = (self.position.1 + self.height).max(other.position.1 + other.height);
+
+        Rect {
+            position: Vector2(left, bottom),
+            width: right - left,
+            height: top - bottom,
+            rotation: 0.0,
+        }
+    }
+}
+
+impl Drawable for Rect {
+    fn point_in_self(&self, point: &Vector2<f32>) -> bool {
+        self.contains_point(point)
+    }
+
+    /// The rectangle itself, or the axis aligned box around its corners
+    /// when tilted.
+    fn bbox(&self) -> Rect {
+        if self.rotation == 0.0 {
+            return self.clone();
+        }
+
+        let center = self.center();
+        let corners = [
+            Vector2(self.position.0, self.position.1),
+            Vector2(self.position.0 + self.width, self.position.1),
+            Vector2(self.position.0, self.position.1 + self.height),
+            Vector2(self.position.0 + self.width, self.position.1 + self.height),
+        ]
+        .map(|corner| corner.rotate_around(&center, self.rotation));
+
+        bbox_of(&corners)
+    }
+
+    fn center(&self) -> Vector2<f32> {
+        self.position.clone() + Vector2(self.width, self.height) / 2.0
+    }
+
+    fn point_on_outline(&self, point: &Vector2<f32>, width: f32) -> bool {
+        if !self.contains_point(point) {
+            return false;
+        }
+
+        let point = self.untilted(point);
+        let max_x = self.position.0 + self.width;
+        let max_y = self.position.1 + self.height;
+
+        point.0 - self.position.0 < width
+            || max_x - point.0 <= width
+            || point.1 - self.position.1 < width
+            || max_y - point.1 <= width
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Circle {
+    pub position: Vector2<f32>,
+    pub radius: f32,
+}
+
+impl Circle {
+    /// Whether the circles overlap or touch.
+    pub fn intersects_circle(&self, other: &Circle) -> bool {
+        let radii = self.radius + other.radius;
+
+        self.position.distance_squared(&other.position) <= radii * radii
+    }
+
+    /// Whether the circle overlaps or touches `rect`, following its
+    /// `rotation`.
+    pub fn intersects_rect(&self, rect: &Rect) -> bool {
+        let center = rect.untilted(&self.position);
+        let closest = Vector2(
+            center
+                .0
+                .clamp(rect.position.0, rect.position.0 + rect.width),
+            center
+                .1
+                .clamp(rect.position.1, rect.position.1 + rect.height),
+        );
+
+        center.distance_squared(&closest) <= self.radius * self.radius
+    }
+}
+
+impl Drawable for Circle {
+    fn point_in_self(&self, point: &Vector2<f32>) -> bool {
+        let distance_squared = point.distance_squared(&self.position);
+
+        distance_squared <= self.radius * self.radius
+    }
+
+    fn point_on_outline(&self, point: &Vector2<f32>, width: f32) -> bool {
+        let distance_squared = point.distance_squared(&self.position);
+        let inner = (self.radius - width).max(0.0);
+
+        inner * inner <= distance_squared && distance_squared <= self.radius * self.radius
+    }
+
+    /// Fades out over one unit centred on the edge, so points exactly on the
+    /// radius are half covered.
+    fn coverage(&self, point: &Vector2<f32>) -> f32 {
+        let distance = point.distance(&self.position);
+
+        (self.radius - distance + 0.5).clamp(0.0, 1.0)
+    }
+
+    fn center(&self) -> Vector2<f32> {
+        self.position.clone()
+    }
+
+    fn bbox(&self) -> Rect {
+        Rect {
+            width: self.radius * 2.0,
+            height: self.radius * 2.0,
+            position: self.position.clone() - Vector2(self.radius, self.radius),
+            rotation: 0.0,
+        }
+    }
+}
+
+/// A hollow circle, covering every point between `inner_radius` and
+/// `outer_radius` from `position`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Ring {
+    pub position: Vector2<f32>,
+    pub inner_radius: f32,
+    pub outer_radius: f32,
+}
+
+impl Drawable for Ring {
+    fn point_in_self(&self, point: &Vector2<f32>) -> bool {
+        let distance_squared = point.distance_squared(&self.position);
+
+        self.inner_radius * self.inner_radius <= distance_squared
+            && distance_squared <= self.outer_radius * self.outer_radius
+    }
+
+    fn bbox(&self) -> Rect {
+        Rect {
+            width: self.outer_radius * 2.0,
+            height: self.outer_radius * 2.0,
+            position: self.position.clone() - Vector2(self.outer_radius, self.outer_radius),
+            rotation: 0.0,
+        }
+    }
+
+    fn center(&self) -> Vector2<f32> {
+        self.position.clone()
+    }
+}
+
+/// Part of a circle's outline, `thickness` units wide and centred on
+/// `radius`. Runs counter-clockwise from `start_angle` to `end_angle`, in
+/// radians from the positive x axis. The angles may lie outside `[0, 2π)`,
+/// an `end_angle` below `start_angle` wraps around through zero.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Arc {
+    pub position: Vector2<f32>,
+    pub radius: f32,
+    pub start_angle: f32,
+    pub end_angle: f32,
+    pub thickness: f32,
+}
+
+impl Drawable for Arc {
+    fn point_in_self(&self, point: &Vector2<f32>) -> bool {
+        let offset = point.clone() - self.position.clone();
+        if (offset.len() - self.radius).abs() > self.thickness / 2.0 {
+            return false;
+        }
+
+        let sweep = self.end_angle - self.start_angle;
+        if sweep.abs() >= TAU {
+            return true;
+        }
+
+        // Both measured counter-clockwise from the start, in [0, 2π)
+        (offset.angle() - self.start_angle).rem_euclid(TAU) <= sweep.rem_euclid(TAU)
+    }
+
+    /// The box of the whole circle, whatever part of it is drawn.
+    fn bbox(&self) -> Rect {
+        let outer = self.radius + self.thickness / 2.0;
+
+        Rect {
+            width: outer * 2.0,
+            height: outer * 2.0,
+            position: self.position.clone() - Vector2(outer, outer),
+            rotation: 0.0,
+        }
+    }
+
+    fn center(&self) -> Vector2<f32> {
+        self.position.clone()
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Ellipse {
+    pub position: Vector2<f32>,
+    pub rx: f32,
+    pub ry: f32,
+}
+
+impl Drawable for Ellipse {
+    fn point_in_self(&self, point: &Vector2<f32>) -> bool {
+        let x_norm = (point.0 - self.position.0) / self.rx;
+        let y_norm = (point.1 - self.position.1) / self.ry;
+
+        (x_norm * x_norm) + (y_norm * y_norm) <= 1.0
+    }
+
+    fn center(&self) -> Vector2<f32> {
+        self.position.clone()
+    }
+
+    fn bbox(&self) -> Rect {
+        Rect {
+            width: self.rx * 2.0,
+            height: self.ry * 2.0,
+            position: self.position.clone() - Vector2(self.rx, self.ry),
+            rotation: 0.0,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Line {
+    pub start: Vector2<f32>,
+    pub end: Vector2<f32>,
+    pub thickness: f32,
+}
+
+impl Drawable for Line {
+    fn point_in_self(&self, point: &Vector2<f32>) -> bool {
+        let direction = self.end.clone() - self.start.clone();
+        let offset = point.clone() - self.start.clone();
+        let len_squared = direction.dot(&direction);
+
+        // Degenerate line, treat it as a single point
+        if len_squared == 0.0 {
+            return point.distance(&self.start) <= self.thickness / 2.0;
+        }
+
+        // Projection of the point onto the segment, 0 at start and 1 at end
+        let t = offset.dot(&direction) / len_squared;
+        if !(0.0..=1.0).contains(&t) {
+            return false;
+        }
+
+        // Distance from the line along its normal
+        let distance = offset.dot(&direction.perpendicular()).abs() / len_squared.sqrt();
+        distance <= self.thickness / 2.0
+    }
+
+    fn bbox(&self) -> Rect {
+        let half = self.thickness / 2.0;
+        let min_x = self.start.0.min(self.end.0) - half;
+        let min_y = self.start.1.min(self.end.1) - half;
+        let max_x = self.start.0.max(self.end.0) + half;
+        let max_y = self.start.1.max(self.end.1) + half;
+
+        Rect {
+            position: Vector2(min_x, min_y),
+            width: max_x - min_x,
+            height: max_y - min_y,
+            rotation: 0.0,
+        }
+    }
+}
+
+/// A quadratic Bézier curve from `p0` to `p2`, bent towards `p1` and
+/// `thickness` units wide. Drawn as `segments` straight pieces, fewer are
+/// faster to test but show corners on tight bends.
+#[derive(Debug, Clone, PartialEq)]
+pub struct QuadBezier {
+    pub p0: Vector2<f32>,
+    pub p1: Vector2<f32>,
+    pub p2: Vector2<f32>,
+    pub thickness: f32,
+    pub segments: usize,
+}
+
+impl QuadBezier {
+    /// The point `t` of the way along the curve, from `p0` at `0.0` to `p2`
+    /// at `1.0`.
+    pub fn point_at(&self, t: f32) -> Vector2<f32> {
+        let a = self.p0.lerp(&self.p1, t);
+        let b = self.p1.lerp(&self.p2, t);
+
+        a.lerp(&b, t)
+    }
+}
+
+impl Drawable for QuadBezier {
+    fn point_in_self(&self, point: &Vector2<f32>) -> bool {
+        let segments = self.segments.max(1);
+        let half = self.thickness / 2.0;
+        let mut start = self.p0.clone();
+
+        for i in 1..=segments {
+            let end = self.point_at(i as f32 / segments as f32);
+            if distance_to_segment(point, &start, &end) <= half {
+                return true;
+            }
+            start = end;
+        }
+
+        false
+    }
+
+    /// The curve never leaves the triangle of its control points, so their
+    /// box always contains it.
+    fn bbox(&self) -> Rect {
+        let half = self.thickness / 2.0;
+        let mut bbox = bbox_of(&[self.p0.clone(), self.p1.clone(), self.p2.clone()]);
+
+        bbox.position -= half;
+        bbox.width += self.thickness;
+        bbox.height += self.thickness;
+        bbox
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Triangle {
+    pub vertices: [Vector2<f32>; 3],
+}
+
+impl Drawable for Triangle {
+    fn point_in_self(&self, point: &Vector2<f32>) -> bool {
+        let [a, b, c] = &self.vertices;
+
+        // Every point is on an edge of a zero-area triangle, so it would
+        // contain the whole plane
+        if edge_side(a, b, c) == 0.0 {
+            return false;
+        }
+
+        let d1 = edge_side(a, b, point);
+        let d2 = edge_side(b, c, point);
+        let d3 = edge_side(c, a, point);
+
+        // Inside when the point is on the same side of every edge, regardless
+        // of winding. Zero means the point lies on an edge.
+        let has_neg = d1 < 0.0 || d2 < 0.0 || d3 < 0.0;
+        let has_pos = d1 > 0.0 || d2 > 0.0 || d3 > 0.0;
+
+        !(has_neg && has_pos)
+    }
+
+    fn bbox(&self) -> Rect {
+        bbox_of(&self.vertices)
+    }
+}
+
+/// A convex polygon. Concave polygons are not supported and will be drawn
+/// incorrectly, as containment is tested against the half-plane of every edge.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Polygon {
+    pub vertices: Vec<Vector2<f32>>,
+}
+
+impl Drawable for Polygon {
+    fn point_in_self(&self, point: &Vector2<f32>) -> bool {
+        // Zero-area polygons are skipped like degenerate triangles
+        if self.vertices.len() < 3 || signed_area(&self.vertices) == 0.0 {
+            return false;
+        }
+
+        let mut has_neg = false;
+        let mut has_pos = false;
+
+        for (i, from) in self.vertices.iter().enumerate() {
+            let to = &self.vertices[(i + 1) % self.vertices.len()];
+            let side = edge_side(from, to, point);
+
+            has_neg |= side < 0.0;
+            has_pos |= side > 0.0;
+
+            if has_neg && has_pos {
+                return false;
+            }
+        }
+
+        true
+    }
+
+    fn bbox(&self) -> Rect {
+        bbox_of(&self.vertices)
+    }
+}
+
+/// A line of text placed in the world, left aligned at `position`. The text
+/// keeps its size at every zoom level.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Text {
+    pub position: Vector2<f32>,
+    pub content: String,
+}
+
+impl Drawable for Text {
+    fn point_in_self(&self, _point: &Vector2<f32>) -> bool {
+        false
+    }
+
+    fn bbox(&self) -> Rect {
+        Rect {
+            position: self.position.clone(),
+            width: 0.0,
+            height: 0.0,
+            rotation: 0.0,
+        }
+    }
+
+    fn text(&self) -> Option<(&Vector2<f32>, &str)> {
+        Some((&self.position, &self.content))
+    }
+}
+
+/// Pre-made ASCII art placed in the world, with its top left character at
+/// `position`. Spaces let whatever is behind the sprite show through.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Sprite {
+    pub position: Vector2<f32>,
+    pub rows: Vec<String>,
+}
+
+impl Drawable for Sprite {
+    fn point_in_self(&self, _point: &Vector2<f32>) -> bool {
+        false
+    }
+
+    fn bbox(&self) -> Rect {
+        Rect {
+            position: self.position.clone(),
+            width: 0.0,
+            height: 0.0,
+            rotation: 0.0,
+        }
+    }
+
+    fn sprite(&self) -> Option<(&Vector2<f32>, &[String])> {
+        Some((&self.position, &self.rows))
+    }
+}
+
+/// Several drawables treated as a single shape, with every child translated
+/// by `offset`.
+pub struct Group {
+    pub children: Vec<Box<dyn Drawable>>,
+    pub offset: Vector2<f32>,
+}
+
+impl Drawable for Group {
+    fn point_in_self(&self, point: &Vector2<f32>) -> bool {
+        let local = point.clone() - self.offset.clone();
+
+        self.children
+            .iter()
+            .any(|child| child.point_in_self(&local))
+    }
+
+    fn bbox(&self) -> Rect {
+        let mut children = self.children.iter().map(|child| child.bbox());
+
+        let mut bbox = match children.next() {
+            Some(first) => children.fold(first, |acc, bbox| acc.union(&bbox)),
+            None => Rect {
+                position: Vector2(0.0, 0.0),
+                width: 0.0,
+                height: 0.0,
+                rotation: 0.0,
+            },
+        };
+
+        bbox.position += self.offset.clone();
+        bbox
+    }
+}
+
+/// One of the built-in geometric shapes, for storing scenes as a plain
+/// `Vec<Shape>` without boxing. Implements `Drawable` by forwarding to the
+/// shape it holds. `Text`, `Sprite` and wrappers such as `Stroke` and `Group`
+/// are not included.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Shape {
+    Rect(Rect),
+    Circle(Circle),
+    Ring(Ring),
+    Arc(Arc),
+    Ellipse(Ellipse),
+    Line(Line),
+    QuadBezier(QuadBezier),
+    Triangle(Triangle),
+    Polygon(Polygon),
+}
+
+/// Calls the same method on whichever shape `$shape` holds.
+macro_rules! dispatch {
+    ($shape:expr, $inner:ident => $call:expr) => {
+        match $shape {
+            Shape::Rect($inner) => $call,
+            Shape::Circle($inner) => $call,
+            Shape::Ring($inner) => $call,
+            Shape::Arc($inner) => $call,
+            Shape::Ellipse($inner) => $call,
+            Shape::Line($inner) => $call,
+            Shape::QuadBezier($inner) => $call,
+            Shape::Triangle($inner) => $call,
+            Shape::Polygon($inner) => $call,
+        }
+    };
+}
+
+impl Drawable for Shape {
+    fn point_in_self(&self, point: &Vector2<f32>) -> bool {
+        dispatch!(self, shape => shape.point_in_self(point))
+    }
+
+    fn bbox(&self) -> Rect {
+        dispatch!(self, shape => shape.bbox())
+    }
+
+    fn center(&self) -> Vector2<f32> {
+        dispatch!(self, shape => shape.center())
+    }
+
+    fn coverage(&self, point: &Vector2<f32>) -> f32 {
+        dispatch!(self, shape => shape.coverage(point))
+    }
+
+    fn point_on_outline(&self, point: &Vector2<f32>, width: f32) -> bool {
+        dispatch!(self, shape => shape.point_on_outline(point, width))
+    }
+}
+
+/// Sign of the cross product of `from -> to` and `from -> point`. Positive
+/// when `point` is to the left of the edge, negative when to the right.
+fn edge_side(from: &Vector2<f32>, to: &Vector2<f32>, point: &Vector2<f32>) -> f32 {
+    (to.clone() - from.clone()).perp_dot(&(point.clone() - from.clone()))
+}
+
+/// Distance from `point` to the closest point between `start` and `end`.
+fn distance_to_segment(point: &Vector2<f32>, start: &Vector2<f32>, end: &Vector2<f32>) -> f32 {
+    let direction = end.clone() - start.clone();
+    let len_squared = direction.len_squared();
+    if len_squared == 0.0 {
+        return point.distance(start);
+    }
+
+    let t = ((point.clone() - start.clone()).dot(&direction) / len_squared).clamp(0.0, 1.0);
+    point.distance(&(start.clone() + direction * t))
+}
+
+/// Signed area of the polygon through `vertices`, positive when they wind
+/// counter-clockwise.
+fn signed_area(vertices: &[Vector2<f32>]) -> f32 {
+    let edges = vertices.iter().zip(vertices.iter().cycle().skip(1));
+
+    edges.map(|(from, to)| from.perp_dot(to)).sum::<f32>() / 2.0
+}
+
+fn bbox_of(points: &[Vector2<f32>]) -> Rect {
+    let mut min = Vector2(f32::INFINITY, f32::INFINITY);
+    let mut max = Vector2(f32::NEG_INFINITY, f32::NEG_INFINITY);
+
+    for point in points {
+        min = Vector2(min.0.min(point.0), min.1.min(point.1));
+        max = Vector2(max.0.max(point.0), max.1.max(point.1));
+    }
+
+    Rect {
+        width: max.0 - min.0,
+        height: max.1 - min.1,
+        position: min,
+        rotation: 0.0,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn line(start: (f32, f32), end: (f32, f32), thickness: f32) -> Line {
+        Line {
+            start: Vector2(start.0, start.1),
+            end: Vector2(end.0, end.1),
+            thickness,
+        }
+    }
+
+    fn rect(x: f32, y: f32, width: f32, height: f32) -> Rect {
+        Rect {
+            position: Vector2(x, y),
+            width,
+            height,
+            rotation: 0.0,
+        }
+    }
+
+    #[test]
+    fn circle_coverage_fades_across_the_edge() {
+        let circle = Circle {
+            position: Vector2(0.0, 0.0),
+            radius: 4.0,
+        };
+
+        assert_eq!(circle.coverage(&Vector2(0.0, 0.0)), 1.0);
+        assert_eq!(circle.coverage(&Vector2(3.5, 0.0)), 1.0);
+        assert_eq!(circle.coverage(&Vector2(0.0, 4.0)), 0.5);
+        assert_eq!(circle.coverage(&Vector2(-4.25, 0.0)), 0.25);
+        assert_eq!(circle.coverage(&Vector2(4.5, 0.0)), 0.0);
+    }
+
+    #[test]
+    fn default_coverage_follows_point_in_self() {
+        let square = Rect {
+            position: Vector2(0.0, 0.0),
+            width: 2.0,
+            height: 2.0,
+            rotation: 0.0,
+        };
+
+        assert_eq!(square.coverage(&Vector2(1.0, 1.0)), 1.0);
+        assert_eq!(square.coverage(&Vector2(2.0, 1.0)), 0.0);
+    }
+
+    #[test]
+    fn horizontal_line() {
+        let line = line((0.0, 0.0), (10.0, 0.0), 2.0);
+
+        assert!(line.point_in_self(&Vector2(5.0, 0.0)));
+        assert!(line.point_in_self(&Vector2(5.0, 1.0)));
+        assert!(line.point_in_self(&Vector2(0.0, -1.0)));
+        assert!(!line.point_in_self(&Vector2(5.0, 1.5)));
+        assert!(!line.point_in_self(&Vector2(10.5, 0.0)));
+        assert!(!line.point_in_self(&Vector2(-0.5, 0.0)));
+    }
+
+    #[test]
+    fn vertical_line() {
+        let line = line((2.0, -5.0), (2.0, 5.0), 1.0);
+
+        assert!(line.point_in_self(&Vector2(2.0, 0.0)));
+        assert!(line.point_in_self(&Vector2(2.5, 5.0)));
+        assert!(!line.point_in_self(&Vector2(3.0, 0.0)));
+        assert!(!line.point_in_self(&Vector2(2.0, 5.5)));
+    }
+
+    #[test]
+    fn diagonal_line() {
+        let line = line((0.0, 0.0), (10.0, 10.0), 1.0);
+
+        assert!(line.point_in_self(&Vector2(5.0, 5.0)));
+        // 0.35 units from the line, along its normal
+        assert!(line.point_in_self(&Vector2(5.25, 4.75)));
+        // 0.7 units from the line
+        assert!(!line.point_in_self(&Vector2(5.5, 4.5)));
+        assert!(!line.point_in_self(&Vector2(11.0, 11.0)));
+    }
+
+    #[test]
+    fn line_bbox_covers_thickness() {
+        let bbox = line((0.0, 4.0), (10.0, 0.0), 2.0).bbox();
+
+        assert_eq!((bbox.position.0, bbox.position.1), (-1.0, -1.0));
+        assert_eq!((bbox.width, bbox.height), (12.0, 6.0));
+    }
+
+    fn triangle(a: (f32, f32), b: (f32, f32), c: (f32, f32)) -> Triangle {
+        Triangle {
+            vertices: [Vector2(a.0, a.1), Vector2(b.0, b.1), Vector2(c.0, c.1)],
+        }
+    }
+
+    #[test]
+    fn triangle_either_winding() {
+        let ccw = triangle((0.0, 0.0), (4.0, 0.0), (0.0, 4.0));
+        let cw = triangle((0.0, 0.0), (0.0, 4.0), (4.0, 0.0));
+
+        for triangle in [ccw, cw] {
+            assert!(triangle.point_in_self(&Vector2(1.0, 1.0)));
+            assert!(!triangle.point_in_self(&Vector2(3.0, 3.0)));
+            assert!(!triangle.point_in_self(&Vector2(-1.0, 1.0)));
+        }
+    }
+
+    #[test]
+    fn triangle_edges_and_vertices_are_inside() {
+        let triangle = triangle((0.0, 0.0), (4.0, 0.0), (0.0, 4.0));
+
+        assert!(triangle.point_in_self(&Vector2(2.0, 0.0)));
+        assert!(triangle.point_in_self(&Vector2(0.0, 2.0)));
+        assert!(triangle.point_in_self(&Vector2(2.0, 2.0)));
+        assert!(triangle.point_in_self(&Vector2(4.0, 0.0)));
+    }
+
+    #[test]
+    fn zero_area_triangle_contains_nothing() {
+        let point = triangle((1.0, 1.0), (1.0, 1.0), (1.0, 1.0));
+        let collinear = triangle((0.0, 0.0), (1.0, 1.0), (2.0, 2.0));
+
+        for triangle in [point, collinear] {
+            assert!(!triangle.point_in_self(&Vector2(1.0, 1.0)));
+            assert!(!triangle.point_in_self(&Vector2(50.0, -20.0)));
+        }
+    }
+
+    #[test]
+    fn zero_area_polygon_contains_nothing() {
+        let polygon = Polygon {
+            vertices: vec![Vector2(0.0, 0.0), Vector2(1.0, 0.0), Vector2(2.0, 0.0)],
+        };
+
+        assert!(!polygon.point_in_self(&Vector2(1.0, 0.0)));
+        assert!(!polygon.point_in_self(&Vector2(5.0, 5.0)));
+    }
+
+    #[test]
+    fn square_polygon() {
+        let square = Polygon {
+            vertices: vec![
+                Vector2(0.0, 0.0),
+                Vector2(2.0, 0.0),
+                Vector2(2.0, 2.0),
+                Vector2(0.0, 2.0),
+            ],
+        };
+
+        assert!(square.point_in_self(&Vector2(1.0, 1.0)));
+        assert!(square.point_in_self(&Vector2(2.0, 1.0)));
+        assert!(square.point_in_self(&Vector2(0.0, 0.0)));
+        assert!(!square.point_in_self(&Vector2(2.5, 1.0)));
+        assert!(!square.point_in_self(&Vector2(1.0, -0.1)));
+
+        let bbox = square.bbox();
+        assert_eq!((bbox.position.0, bbox.position.1), (0.0, 0.0));
+        assert_eq!((bbox.width, bbox.height), (2.0, 2.0));
+    }
+
+    #[test]
+    fn hexagon_polygon() {
+        // Clockwise, with vertices on the axes 2 units out. The flat edges
+        // are at y = +-sqrt(3)
+        let hexagon = Polygon {
+            vertices: (0..6)
+                .map(|i| {
+                    let angle = -(i as f32) * std::f32::consts::TAU / 6.0;
+                    Vector2(angle.cos(), angle.sin()) * 2.0
+                })
+                .collect(),
+        };
+        let flat = 3.0_f32.sqrt();
+
+        assert!(hexagon.point_in_self(&Vector2(0.0, 0.0)));
+        assert!(hexagon.point_in_self(&Vector2(1.9, 0.0)));
+        assert!(hexagon.point_in_self(&Vector2(0.0, flat - 1e-4)));
+        assert!(hexagon.point_in_self(&Vector2(1.5, flat / 2.0)));
+        assert!(!hexagon.point_in_self(&Vector2(0.0, flat + 1e-4)));
+        assert!(!hexagon.point_in_self(&Vector2(1.8, 1.0)));
+        assert!(!hexagon.point_in_self(&Vector2(2.1, 0.0)));
+    }
+
+    #[test]
+    fn polygon_vertices_are_inside() {
+        let hexagon = Polygon {
+            vertices: vec![
+                Vector2(2.0, 0.0),
+                Vector2(1.0, 2.0),
+                Vector2(-1.0, 2.0),
+                Vector2(-2.0, 0.0),
+                Vector2(-1.0, -2.0),
+                Vector2(1.0, -2.0),
+            ],
+        };
+
+        for vertex in &hexagon.vertices {
+            assert!(hexagon.point_in_self(vertex));
+        }
+        assert!(hexagon.point_in_self(&Vector2(0.0, 2.0)));
+        assert!(!hexagon.point_in_self(&Vector2(1.6, 1.0)));
+    }
+
+    #[test]
+    fn center_defaults_to_the_bbox_center() {
+        let rect = Rect {
+            position: Vector2(2.0, -4.0),
+            width: 6.0,
+            height: 2.0,
+            rotation: 0.0,
+        };
+        assert_eq!(rect.center(), Vector2(5.0, -3.0));
+
+        let triangle = Triangle {
+            vertices: [Vector2(0.0, 0.0), Vector2(4.0, 0.0), Vector2(0.0, 3.0)],
+        };
+        assert_eq!(triangle.center(), Vector2(2.0, 1.5));
+
+        let circle = Circle {
+            position: Vector2(1.0, 1.0),
+            radius: 3.0,
+        };
+        assert_eq!(circle.center(), Vector2(1.0, 1.0));
+        assert_eq!(
+            line((0.0, 0.0), (4.0, 2.0), 1.0).center(),
+            Vector2(2.0, 1.0)
+        );
+
+        let stroke = Stroke {
+            shape: circle,
+            width: 1.0,
+        };
+        assert_eq!(stroke.center(), Vector2(1.0, 1.0));
+    }
+
+    fn arc(start_angle: f32, end_angle: f32) -> Arc {
+        Arc {
+            position: Vector2(0.0, 0.0),
+            radius: 4.0,
+            start_angle,
+            end_angle,
+            thickness: 1.0,
+        }
+    }
+
+    #[test]
+    fn arc_covers_its_angles_near_the_radius() {
+        use std::f32::consts::{FRAC_PI_2, PI};
+
+        let quarter = arc(0.0, FRAC_PI_2);
+        assert!(quarter.point_in_self(&Vector2(4.0, 0.1)));
+        assert!(quarter.point_in_self(&Vector2(0.1, 4.4)));
+        assert!(quarter.point_in_self(&Vector2(2.5, 2.5)));
+        assert!(!quarter.point_in_self(&Vector2(1.0, 1.0)));
+        assert!(!quarter.point_in_self(&Vector2(5.0, 0.1)));
+        assert!(!quarter.point_in_self(&Vector2(-4.0, 0.1)));
+        assert!(!quarter.point_in_self(&Vector2(0.1, -4.0)));
+
+        // From just below the x axis round to just above it
+        let wrapping = arc(-0.5, 0.5);
+        let also_wrapping = arc(2.0 * PI - 0.5, 0.5);
+        for arc in [wrapping, also_wrapping] {
+            assert!(arc.point_in_self(&Vector2(4.0, -1.0)));
+            assert!(arc.point_in_self(&Vector2(4.0, 1.0)));
+            assert!(!arc.point_in_self(&Vector2(0.0, 4.0)));
+            assert!(!arc.point_in_self(&Vector2(-4.0, 0.0)));
+        }
+
+        let full = arc(1.0, 1.0 + 2.0 * PI);
+        assert!(full.point_in_self(&Vector2(-4.0, 0.0)));
+        assert_eq!(full.bbox().position, Vector2(-4.5, -4.5));
+    }
+
+    #[test]
+    fn ring_covers_between_its_radii() {
+        let ring = Ring {
+            position: Vector2(1.0, 1.0),
+            inner_radius: 2.0,
+            outer_radius: 5.0,
+        };
+
+        assert!(!ring.point_in_self(&Vector2(1.0, 1.0)));
+        assert!(!ring.point_in_self(&Vector2(2.5, 1.0)));
+        assert!(ring.point_in_self(&Vector2(3.0, 1.0)));
+        assert!(ring.point_in_self(&Vector2(1.0, -3.0)));
+        assert!(ring.point_in_self(&Vector2(6.0, 1.0)));
+        assert!(!ring.point_in_self(&Vector2(5.0, 5.0)));
+        assert_eq!(ring.bbox().position, Vector2(-4.0, -4.0));
+        assert_eq!(ring.bbox().width, 10.0);
+    }
+
+    #[test]
+    fn bezier_follows_the_curve() {
+        let curve = QuadBezier {
+            p0: Vector2(0.0, 0.0),
+            p1: Vector2(5.0, 10.0),
+            p2: Vector2(10.0, 0.0),
+            thickness: 1.0,
+            segments: 16,
+        };
+
+        assert_eq!(curve.point_at(0.5), Vector2(5.0, 5.0));
+        assert!(curve.point_in_self(&Vector2(0.0, 0.0)));
+        assert!(curve.point_in_self(&Vector2(5.0, 5.3)));
+        assert!(curve.point_in_self(&Vector2(10.0, 0.0)));
+        // Inside the control triangle, but away from the curve
+        assert!(!curve.point_in_self(&Vector2(5.0, 3.0)));
+        assert!(!curve.point_in_self(&Vector2(5.0, 8.0)));
+
+        let bbox = curve.bbox();
+        assert_eq!(bbox.position, Vector2(-0.5, -0.5));
+        assert_eq!((bbox.width, bbox.height), (11.0, 11.0));
+
+        // A single segment is a straight line between the ends
+        let coarse = QuadBezier {
+            segments: 1,
+            ..curve
+        };
+        assert!(coarse.point_in_self(&Vector2(5.0, 0.0)));
+        assert!(!coarse.point_in_self(&Vector2(5.0, 5.0)));
+    }
+
+    #[test]
+    fn transformed_scales_rotates_and_moves_the_inner_shape() {
+        use std::f32::consts::FRAC_PI_2;
+
+        let mut oval = Transformed::new(Circle {
+            position: Vector2(0.0, 0.0),
+            radius: 1.0,
+        });
+        oval.scale = Vector2(3.0, 1.0);
+        oval.translate = Vector2(10.0, 0.0);
+
+        assert!(oval.point_in_self(&Vector2(12.5, 0.0)));
+        assert!(!oval.point_in_self(&Vector2(10.0, 1.5)));
+        assert_eq!(oval.center(), Vector2(10.0, 0.0));
+        assert_eq!(oval.bbox().position, Vector2(7.0, -1.0));
+        assert_eq!((oval.bbox().width, oval.bbox().height), (6.0, 2.0));
+
+        // A quarter turn stands the oval upright
+        oval.rotate = FRAC_PI_2;
+        assert!(oval.point_in_self(&Vector2(10.0, 2.5)));
+        assert!(!oval.point_in_self(&Vector2(12.5, 0.0)));
+        let bbox = oval.bbox();
+        assert!(bbox.position.approx_eq(&Vector2(9.0, -3.0), 1e-5));
+        assert!((bbox.width - 2.0).abs() < 1e-5 && (bbox.height - 6.0).abs() < 1e-5);
+
+        let point = Vector2(3.0, -4.0);
+        assert!(oval.invert(&oval.apply(&point)).approx_eq(&point, 1e-5));
+    }
+
+    #[test]
+    fn rects_default_to_untilted() {
+        let tall = Rect {
+            height: 3.0,
+            ..Default::default()
+        };
+
+        assert_eq!(tall, Rect::new(Vector2::ZERO, 0.0, 3.0));
+        assert_eq!(tall.rotation, 0.0);
+        assert_eq!(
+            Rect::new(Vector2(1.0, 2.0), 3.0, 4.0),
+            rect(1.0, 2.0, 3.0, 4.0)
+        );
+    }
+
+    #[test]
+    fn tilted_rect_rotates_around_its_center() {
+        use std::f32::consts::FRAC_PI_4;
+
+        let diamond = Rect {
+            position: Vector2(-1.0, -1.0),
+            width: 2.0,
+            height: 2.0,
+            rotation: FRAC_PI_4,
+        };
+
+        assert!(diamond.point_in_self(&Vector2(0.0, 1.3)));
+        assert!(diamond.point_in_self(&Vector2(-1.3, 0.0)));
+        assert!(!diamond.point_in_self(&Vector2(0.9, 0.9)));
+        assert!(diamond.contains_point(&Vector2(0.0, 1.3)));
+        assert!(!diamond.contains_point(&Vector2(0.9, 0.9)));
+        assert_eq!(diamond.center(), Vector2(0.0, 0.0));
+
+        let bbox = diamond.bbox();
+        let half_diagonal = std::f32::consts::SQRT_2;
+        assert!(bbox
+            .position
+            .approx_eq(&Vector2(-half_diagonal, -half_diagonal), 1e-5));
+        assert!((bbox.width - 2.0 * half_diagonal).abs() < 1e-5);
+        assert_eq!(bbox.rotation, 0.0);
+
+        // Outlines follow the tilt too
+        assert!(diamond.point_on_outline(&Vector2(0.0, 1.3), 0.2));
+        assert!(!diamond.point_on_outline(&Vector2(0.0, 0.0), 0.2));
+    }
+
+    #[test]
+    fn shape_forwards_to_the_shape_it_holds() {
+        let circle = Circle {
+            position: Vector2(1.0, 1.0),
+            radius: 2.0,
+        };
+        let shapes = [
+            Shape::Circle(circle.clone()),
+            Shape::Line(line((0.0, 0.0), (4.0, 0.0), 1.0)),
+        ];
+
+        assert_eq!(shapes[0].bbox(), circle.bbox());
+        assert_eq!(shapes[0].coverage(&Vector2(1.0, 3.0)), 0.5);
+        assert!(shapes[1].point_in_self(&Vector2(2.0, 0.25)));
+        assert!(!shapes
+            .iter()
+            .any(|shape| shape.point_in_self(&Vector2(5.0, 5.0))));
+    }
+
+    #[test]
+    fn circles_intersect_when_touching_or_overlapping() {
+        let circle = |x: f32, radius: f32| Circle {
+            position: Vector2(x, 0.0),
+            radius,
+        };
+
+        assert!(circle(0.0, 2.0).intersects_circle(&circle(3.0, 1.0)));
+        assert!(circle(0.0, 2.0).intersects_circle(&circle(1.0, 2.0)));
+        assert!(circle(0.0, 1.0).intersects_circle(&circle(0.0, 3.0)));
+        assert!(!circle(0.0, 2.0).intersects_circle(&circle(3.5, 1.0)));
+    }
+
+    #[test]
+    fn circle_intersects_rect_at_the_closest_point() {
+        use std::f32::consts::FRAC_PI_4;
+
+        let square = Rect {
+            position: Vector2(0.0, 0.0),
+            width: 2.0,
+            height: 2.0,
+            rotation: 0.0,
+        };
+        let circle = |x: f32, y: f32| Circle {
+            position: Vector2(x, y),
+            radius: 1.0,
+        };
+
+        assert!(circle(1.0, 1.0).intersects_rect(&square));
+        assert!(circle(2.5, 1.0).intersects_rect(&square));
+        assert!(circle(-1.0, 1.0).intersects_rect(&square));
+        assert!(circle(2.6, 2.6).intersects_rect(&square));
+        assert!(!circle(2.8, 2.8).intersects_rect(&square));
+        assert!(!circle(1.0, -1.5).intersects_rect(&square));
+
+        // Tilted by 45 degrees, the corner reaches sqrt(2) from the center
+        let diamond = Rect {
+            rotation: FRAC_PI_4,
+            ..square
+        };
+        assert!(circle(1.0, 3.3).intersects_rect(&diamond));
+        assert!(!circle(2.6, 2.6).intersects_rect(&diamond));
+    }
+
+    #[test]
+    fn disjoint_rects_do_not_intersect() {
+        let square = rect(0.0, 0.0, 2.0, 2.0);
+
+        assert_eq!(square.intersection(&rect(3.0, 0.0, 1.0, 1.0)), None);
+        assert_eq!(square.intersection(&rect(0.0, -2.5, 2.0, 2.0)), None);
+        assert_eq!(
+            square.union(&rect(3.0, 0.0, 1.0, 1.0)),
+            rect(0.0, 0.0, 4.0, 2.0)
+        );
+    }
+
+    #[test]
+    fn touching_rects_intersect_along_the_shared_edge() {
+        let square = rect(0.0, 0.0, 2.0, 2.0);
+
+        assert_eq!(
+            square.intersection(&rect(2.0, 1.0, 2.0, 2.0)),
+            Some(rect(2.0, 1.0, 0.0, 1.0))
+        );
+        assert_eq!(
+            square.intersection(&rect(2.0, 2.0, 1.0, 1.0)),
+            Some(rect(2.0, 2.0, 0.0, 0.0))
+        );
+    }
+
+    #[test]
+    fn nested_rects_intersect_in_the_inner_and_unite_in_the_outer() {
+        let outer = rect(0.0, 0.0, 4.0, 4.0);
+        let inner = rect(1.0, 1.0, 1.0, 2.0);
+
+        assert_eq!(outer.intersection(&inner), Some(inner.clone()));
+        assert_eq!(inner.intersection(&outer), Some(inner.clone()));
+        assert_eq!(outer.union(&inner), outer);
+        assert_eq!(inner.union(&outer), outer);
+    }
+
+    #[test]
+    fn zero_area_rects_still_count() {
+        let square = rect(0.0, 0.0, 2.0, 2.0);
+        let point = rect(5.0, 3.0, 0.0, 0.0);
+
+        assert_eq!(square.union(&point), rect(0.0, 0.0, 5.0, 3.0));
+        assert_eq!(square.intersection(&point), None);
+        assert_eq!(
+            square.intersection(&rect(1.0, 1.0, 0.0, 0.0)),
+            Some(rect(1.0, 1.0, 0.0, 0.0))
+        );
+    }
+
+    #[test]
+    fn rect_contains_its_left_and_bottom_edges_only() {
+        let square = rect(0.0, 0.0, 2.0, 2.0);
+
+        assert!(square.contains_point(&Vector2(1.0, 1.0)));
+        assert!(square.contains_point(&Vector2(0.0, 0.0)));
+        assert!(square.contains_point(&Vector2(0.0, 1.0)));
+        assert!(square.contains_point(&Vector2(1.0, 0.0)));
+        assert!(!square.contains_point(&Vector2(2.0, 1.0)));
+        assert!(!square.contains_point(&Vector2(1.0, 2.0)));
+        assert!(!square.contains_point(&Vector2(-0.01, 1.0)));
+        assert!(!square.contains_point(&Vector2(1.0, -0.01)));
+        assert!(square.contains_point(&Vector2(1.99, 1.99)));
+    }
+
+    #[test]
+    fn rect_contains_nested_rects_edges_included() {
+        let outer = rect(0.0, 0.0, 4.0, 4.0);
+
+        assert!(outer.contains_rect(&rect(1.0, 1.0, 2.0, 2.0)));
+        assert!(outer.contains_rect(&outer));
+        assert!(outer.contains_rect(&rect(2.0, 0.0, 2.0, 4.0)));
+        assert!(!outer.contains_rect(&rect(3.0, 3.0, 2.0, 2.0)));
+        assert!(!outer.contains_rect(&rect(-1.0, 1.0, 2.0, 2.0)));
+        assert!(!rect(1.0, 1.0, 2.0, 2.0).contains_rect(&outer));
+    }
+
+    #[test]
+    fn ellipse_reaches_each_semi_axis() {
+        let ellipse = Ellipse {
+            position: Vector2(1.0, 1.0),
+            rx: 4.0,
+            ry: 2.0,
+        };
+
+        assert!(ellipse.point_in_self(&Vector2(1.0, 1.0)));
+        for (inside, outside) in [
+            ((4.9, 1.0), (5.1, 1.0)),
+            ((-2.9, 1.0), (-3.1, 1.0)),
+            ((1.0, 2.9), (1.0, 3.1)),
+            ((1.0, -0.9), (1.0, -1.1)),
+        ] {
+            assert!(ellipse.point_in_self(&Vector2(inside.0, inside.1)));
+            assert!(!ellipse.point_in_self(&Vector2(outside.0, outside.1)));
+        }
+        assert!(ellipse.point_in_self(&Vector2(5.0, 1.0)));
+        assert!(!ellipse.point_in_self(&Vector2(4.0, 2.5)));
+    }
+
+    #[test]
+    fn ellipse_bbox_spans_both_diameters() {
+        let ellipse = Ellipse {
+            position: Vector2(1.0, 1.0),
+            rx: 4.0,
+            ry: 2.0,
+        };
+
+        assert_eq!(ellipse.bbox(), rect(-3.0, -1.0, 8.0, 4.0));
+        assert_eq!(ellipse.center(), Vector2(1.0, 1.0));
+    }
+
+    #[test]
+    fn ellipse_outline_hugs_the_edge() {
+        let ellipse = Ellipse {
+            position: Vector2(0.0, 0.0),
+            rx: 4.0,
+            ry: 2.0,
+        };
+
+        assert!(ellipse.point_on_outline(&Vector2(3.5, 0.0), 1.0));
+        assert!(ellipse.point_on_outline(&Vector2(0.0, -1.5), 1.0));
+        assert!(!ellipse.point_on_outline(&Vector2(0.0, 0.0), 1.0));
+        assert!(!ellipse.point_on_outline(&Vector2(4.5, 0.0), 1.0));
+    }
+
+    #[test]
+    fn snowman_group_moves_its_children_together() {
+        let ball = |y: f32, radius: f32| -> Box<dyn Drawable> {
+            Box::new(Circle {
+                position: Vector2(0.0, y),
+                radius,
+            })
+        };
+        let snowman = Group {
+            children: vec![ball(0.0, 3.0), ball(5.0, 2.0), ball(8.0, 1.0)],
+            offset: Vector2(10.0, 0.0),
+        };
+
+        for y in [0.0, 5.0, 8.0] {
+            assert!(snowman.point_in_self(&Vector2(10.0, y)));
+            assert!(!snowman.point_in_self(&Vector2(0.0, y)));
+        }
+        assert!(!snowman.point_in_self(&Vector2(12.5, 5.0)));
+        assert!(!snowman.point_in_self(&Vector2(10.0, 9.5)));
+
+        let children = snowman.children.iter().map(|child| child.bbox());
+        let union = children.reduce(|acc, bbox| acc.union(&bbox)).unwrap();
+        assert_eq!(union, rect(-3.0, -3.0, 6.0, 12.0));
+        assert_eq!(snowman.bbox(), rect(7.0, -3.0, 6.0, 12.0));
+    }
+
+    #[test]
+    fn empty_group_is_a_point_at_its_offset() {
+        let empty = Group {
+            children: Vec::new(),
+            offset: Vector2(2.0, -1.0),
+        };
+
+        assert!(!empty.point_in_self(&Vector2(2.0, -1.0)));
+        assert_eq!(empty.bbox(), rect(2.0, -1.0, 0.0, 0.0));
+    }
+
+    #[test]
+    fn stroked_circle_keeps_only_the_edge() {
+        let ring = Stroke {
+            shape: Circle {
+                position: Vector2(0.0, 0.0),
+                radius: 4.0,
+            },
+            width: 1.0,
+        };
+
+        assert!(ring.point_in_self(&Vector2(3.5, 0.0)));
+        assert!(ring.point_in_self(&Vector2(0.0, -4.0)));
+        assert!(!ring.point_in_self(&Vector2(2.5, 0.0)));
+        assert!(!ring.point_in_self(&Vector2(0.0, 0.0)));
+        assert!(!ring.point_in_self(&Vector2(4.5, 0.0)));
+        assert_eq!(ring.bbox(), ring.shape.bbox());
+    }
+
+    #[test]
+    fn stroked_rect_keeps_only_the_edge() {
+        let frame = Stroke {
+            shape: rect(0.0, 0.0, 6.0, 4.0),
+            width: 1.0,
+        };
+
+        for (x, y) in [(0.5, 2.0), (5.5, 2.0), (3.0, 0.0), (3.0, 3.5)] {
+            assert!(frame.point_in_self(&Vector2(x, y)), "({x}, {y})");
+        }
+        for (x, y) in [(3.0, 2.0), (1.5, 1.5), (7.0, 2.0), (3.0, 4.0)] {
+            assert!(!frame.point_in_self(&Vector2(x, y)), "({x}, {y})");
+        }
+    }
+}
@@ -0,0 +1,67 @@
+use std::{
+    io::stdout,
+    panic,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Once,
+    },
+};
+
+use crossterm::{
+    cursor::{Hide, Show},
+    execute,
+    terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
+};
+
+/// Guards currently alive, so the panic hook only touches the terminal
+/// while one of them has it set up.
+static ACTIVE_GUARDS: AtomicUsize = AtomicUsize::new(0);
+
+static INSTALL_HOOK: Once = Once::new();
+
+/// Puts the terminal into raw mode on the alternate screen, with the cursor
+/// hidden, until dropped. The terminal and the user's previous screen are
+/// restored even if the program returns early or panics while the guard is
+/// alive.
+pub struct TerminalGuard {
+    _private: (),
+}
+
+impl TerminalGuard {
+    pub fn new() -> std::io::Result<Self> {
+        enable_raw_mode()?;
+
+        // Dropping the guard undoes whatever was set up before a failure
+        ACTIVE_GUARDS.fetch_add(1, Ordering::SeqCst);
+        let guard = Self { _private: () };
+        execute!(stdout(), EnterAlternateScreen, Hide)?;
+
+        // The default hook prints the panic message before unwinding drops
+        // the guard, so it would land on the alternate screen and vanish.
+        // Installed once, however many guards are created
+        INSTALL_HOOK.call_once(|| {
+            let previous = panic::take_hook();
+            panic::set_hook(Box::new(move |info| {
+                if ACTIVE_GUARDS.load(Ordering::SeqCst) > 0 {
+                    restore();
+                }
+                previous(info);
+            }));
+        });
+
+        Ok(guard)
+    }
+}
+
+impl Drop for TerminalGuard {
+    fn drop(&mut self) {
+        ACTIVE_GUARDS.fetch_sub(1, Ordering::SeqCst);
+        restore();
+    }
+}
+
+fn restore() {
+    // Nothing useful can be done with an error while restoring
+    let _ = execute!(stdout(), Show, LeaveAlternateScreen);
+    let _ = disable_raw_mode();
+}
@@ -1,8 +1,115 @@
-use std::ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Sub, SubAssign};
+use std::{
+    fmt,
+    iter::Sum,
+    ops::{Add, AddAssign, Div, DivAssign, Index, IndexMut, Mul, MulAssign, Neg, Sub, SubAssign},
+};
 
-#[derive(Debug, Clone)]
+/// Lengths at or below this are treated as zero by `normalise`.
+pub const NORMALISE_EPSILON: f32 = 1e-6;
+
+/// Numeric types usable as `Vector2` components. Implemented for the
+/// primitive integer and float types.
+pub trait Scalar:
+    Copy
+    + PartialOrd
+    + Add<Output = Self>
+    + Sub<Output = Self>
+    + Mul<Output = Self>
+    + Div<Output = Self>
+    + AddAssign
+    + SubAssign
+    + MulAssign
+    + DivAssign
+{
+}
+
+macro_rules! impl_scalar {
+    ($($t:ty),*) => {
+        $(impl Scalar for $t {})*
+    };
+}
+
+impl_scalar!(i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize, f32, f64);
+
+#[derive(Debug, Clone, PartialEq)]
 pub struct Vector2<T>(pub T, pub T);
 
+impl<T: Scalar> Vector2<T> {
+    /// A vector with both components set to `v`.
+    pub fn splat(v: T) -> Self {
+        Self(v, v)
+    }
+
+    pub fn dot(&self, rhs: &Self) -> T {
+        self.0 * rhs.0 + self.1 * rhs.1
+    }
+
+    /// Applies `f` to both components, such as `v.map(f32::abs)`.
+    pub fn map(&self, f: impl Fn(T) -> T) -> Self {
+        Self(f(self.0), f(self.1))
+    }
+
+    /// A copy with the x component replaced.
+    pub fn with_x(&self, x: T) -> Self {
+        Self(x, self.1)
+    }
+
+    /// A copy with the y component replaced.
+    pub fn with_y(&self, y: T) -> Self {
+        Self(self.0, y)
+    }
+
+    /// The 2D cross product, the z component of the 3D cross product of the
+    /// two vectors. Positive when `other` is counter-clockwise from `self`,
+    /// negative when clockwise and zero when they are parallel.
+    pub fn perp_dot(&self, other: &Self) -> T {
+        self.0 * other.1 - self.1 * other.0
+    }
+
+    /// Squared length of the vector. Cheaper than `len` when only comparing
+    /// magnitudes.
+    pub fn len_squared(&self) -> T {
+        self.0 * self.0 + self.1 * self.1
+    }
+
+    pub fn distance_squared(&self, other: &Self) -> T {
+        let x_diff = self.0 - other.0;
+        let y_diff = self.1 - other.1;
+
+        x_diff * x_diff + y_diff * y_diff
+    }
+
+    /// The smaller of each pair of components.
+    pub fn min(&self, other: &Self) -> Self {
+        Self(
+            if other.0 < self.0 { other.0 } else { self.0 },
+            if other.1 < self.1 { other.1 } else { self.1 },
+        )
+    }
+
+    /// The larger of each pair of components.
+    pub fn max(&self, other: &Self) -> Self {
+        Self(
+            if other.0 > self.0 { other.0 } else { self.0 },
+            if other.1 > self.1 { other.1 } else { self.1 },
+        )
+    }
+
+    /// Limits each component to the range given by the matching components
+    /// of `lo` and `hi`, for example to keep a point inside a box.
+    pub fn clamp(&self, lo: &Self, hi: &Self) -> Self {
+        self.max(lo).min(hi)
+    }
+}
+
+impl<T: Scalar + Neg<Output = T>> Vector2<T> {
+    /// The vector rotated 90 degrees counter-clockwise, with the same
+    /// length.
+    pub fn perpendicular(&self) -> Self {
+        Self(-self.1, self.0)
+    }
+}
+
 impl Vector2<f32> {
     pub const ZERO: Vector2<f32> = Vector2(0.0, 0.0);
     pub const UP: Vector2<f32> = Vector2(0.0, 1.0);
@@ -10,112 +117,688 @@ impl Vector2<f32> {
     pub const LEFT: Vector2<f32> = Vector2(-1.0, 0.0);
     pub const RIGHT: Vector2<f32> = Vector2(1.0, 0.0);
 
-    pub fn dot(&self, rhs: &Self) -> f32 {
-        self.0 * rhs.0 + self.1 * rhs.1
+    /// The unit vector pointing `radians` counter-clockwise from the
+    /// positive x axis. The inverse of `angle`.
+    pub fn from_angle(radians: f32) -> Self {
+        let (sin, cos) = radians.sin_cos();
+
+        Self(cos, sin)
+    }
+
+    /// The point `radius` away from the origin in the direction `angle`,
+    /// in radians counter-clockwise from the positive x axis.
+    pub fn from_polar(radius: f32, angle: f32) -> Self {
+        Self::from_angle(angle) * radius
+    }
+
+    /// The vector as `(radius, angle)`, the inverse of `from_polar`. The zero
+    /// vector has no direction and returns `(0.0, 0.0)`.
+    pub fn to_polar(&self) -> (f32, f32) {
+        if self.0 == 0.0 && self.1 == 0.0 {
+            return (0.0, 0.0);
+        }
+
+        (self.len(), self.angle())
     }
 
     pub fn len(&self) -> f32 {
-        (self.0 * self.0 + self.1 * self.1).sqrt()
+        self.len_squared().sqrt()
+    }
+
+    pub fn distance(&self, other: &Self) -> f32 {
+        self.distance_squared(other).sqrt()
     }
 
+    /// Distance travelled between the points when only moving along the
+    /// axes, `|dx| + |dy|`.
+    pub fn manhattan_distance(&self, other: &Self) -> f32 {
+        (self.0 - other.0).abs() + (self.1 - other.1).abs()
+    }
+
+    /// Distance between the points when diagonal steps count as one,
+    /// `max(|dx|, |dy|)`.
+    pub fn chebyshev_distance(&self, other: &Self) -> f32 {
+        (self.0 - other.0).abs().max((self.1 - other.1).abs())
+    }
+
+    pub fn abs(&self) -> Self {
+        self.map(f32::abs)
+    }
+
+    /// Rounds both components down, for snapping to the cell grid.
+    pub fn floor(&self) -> Self {
+        self.map(f32::floor)
+    }
+
+    pub fn ceil(&self) -> Self {
+        self.map(f32::ceil)
+    }
+
+    /// Rounds both components to the nearest integer, halfway cases away
+    /// from zero like `f32::round`.
+    pub fn round(&self) -> Self {
+        self.map(f32::round)
+    }
+
+    /// Rotates the vector counter-clockwise by `radians`.
+    pub fn rotate(&self, radians: f32) -> Self {
+        let (sin, cos) = radians.sin_cos();
+
+        Self(self.0 * cos - self.1 * sin, self.0 * sin + self.1 * cos)
+    }
+
+    /// Rotates the point counter-clockwise by `radians` around `pivot`.
+    pub fn rotate_around(&self, pivot: &Self, radians: f32) -> Self {
+        let offset = Self(self.0 - pivot.0, self.1 - pivot.1).rotate(radians);
+
+        Self(pivot.0 + offset.0, pivot.1 + offset.1)
+    }
+
+    /// Angle of the vector in radians, measured counter-clockwise from the
+    /// positive x axis, in the range `[-PI, PI]`. The zero vector has no
+    /// direction and returns `0.0`.
+    pub fn angle(&self) -> f32 {
+        if self.0 == 0.0 && self.1 == 0.0 {
+            return 0.0;
+        }
+
+        self.1.atan2(self.0)
+    }
+
+    /// Signed angle in radians to rotate `self` counter-clockwise onto
+    /// `other`, in the range `[-PI, PI]`. Returns `0.0` if either vector is
+    /// zero.
+    pub fn angle_between(&self, other: &Self) -> f32 {
+        if self.len_squared() == 0.0 || other.len_squared() == 0.0 {
+            return 0.0;
+        }
+
+        self.perp_dot(other).atan2(self.dot(other))
+    }
+
+    /// Reflects the vector off a surface with the given normal, as a ball
+    /// bouncing off a wall. `normal` must be normalised, otherwise the result
+    /// is scaled as well as reflected.
+    pub fn reflect(&self, normal: &Self) -> Self {
+        self.clone() - normal.clone() * (2.0 * self.dot(normal))
+    }
+
+    /// The part of the vector pointing along `other`. Returns
+    /// `Vector2::ZERO` if `other` is zero, as it has no direction.
+    pub fn project_onto(&self, other: &Self) -> Self {
+        let len_squared = other.len_squared();
+        if len_squared == 0.0 {
+            return Self::ZERO;
+        }
+
+        other.clone() * (self.dot(other) / len_squared)
+    }
+
+    /// The part of the vector perpendicular to `other`, so that
+    /// `project_onto(other) + reject_from(other)` is the vector itself.
+    /// Returns `Vector2::ZERO` if `other` is zero, like `project_onto`.
+    pub fn reject_from(&self, other: &Self) -> Self {
+        if other.len_squared() == 0.0 {
+            return Self::ZERO;
+        }
+
+        self.clone() - self.project_onto(other)
+    }
+
+    /// The point halfway between `self` and `other`.
+    pub fn midpoint(&self, other: &Self) -> Self {
+        Self((self.0 + other.0) / 2.0, (self.1 + other.1) / 2.0)
+    }
+
+    /// Linearly interpolates towards `target`, returning `self` at `t = 0.0`
+    /// and `target` at `t = 1.0`. `t` is not clamped, values outside `[0, 1]`
+    /// extrapolate past either end.
+    pub fn lerp(&self, target: &Self, t: f32) -> Self {
+        Self(
+            self.0 + (target.0 - self.0) * t,
+            self.1 + (target.1 - self.1) * t,
+        )
+    }
+
+    /// Whether both components are within `epsilon` of `other`'s. Prefer this
+    /// over `==` when comparing the results of floating point math.
+    pub fn approx_eq(&self, other: &Self, epsilon: f32) -> bool {
+        (self.0 - other.0).abs() <= epsilon && (self.1 - other.1).abs() <= epsilon
+    }
+
+    /// Scales the vector to unit length. Vectors shorter than
+    /// `NORMALISE_EPSILON` have no usable direction and are left unchanged.
     pub fn normalise(&mut self) {
-        *self /= self.len();
+        let len = self.len();
+        if len > NORMALISE_EPSILON {
+            *self /= len;
+        }
     }
 
+    /// A unit vector in the same direction, or `Vector2::ZERO` if the vector
+    /// is shorter than `NORMALISE_EPSILON`.
     pub fn normalised(&self) -> Self {
-        self.clone() / self.len()
+        self.clone().to_normalised()
     }
 
-    pub fn to_normalised(mut self) -> Self {
-        self /= self.len();
-        self
+    pub fn to_normalised(self) -> Self {
+        let len = self.len();
+        if len > NORMALISE_EPSILON {
+            self / len
+        } else {
+            Self::ZERO
+        }
+    }
+}
+
+/// Formats as `(x, y)`. A precision such as `{:.2}` is applied to both
+/// components.
+impl<T: fmt::Display> fmt::Display for Vector2<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match f.precision() {
+            Some(precision) => write!(f, "({:.*}, {:.*})", precision, self.0, precision, self.1),
+            None => write!(f, "({}, {})", self.0, self.1),
+        }
+    }
+}
+
+impl<T> From<(T, T)> for Vector2<T> {
+    fn from((x, y): (T, T)) -> Self {
+        Self(x, y)
+    }
+}
+
+impl<T> From<Vector2<T>> for (T, T) {
+    fn from(v: Vector2<T>) -> Self {
+        (v.0, v.1)
+    }
+}
+
+impl<T> From<[T; 2]> for Vector2<T> {
+    fn from([x, y]: [T; 2]) -> Self {
+        Self(x, y)
+    }
+}
+
+impl<T> From<Vector2<T>> for [T; 2] {
+    fn from(v: Vector2<T>) -> Self {
+        [v.0, v.1]
+    }
+}
+
+/// Indexes the components by axis, `0` for x and `1` for y.
+///
+/// # Panics
+///
+/// Panics for any other index.
+impl<T> Index<usize> for Vector2<T> {
+    type Output = T;
+
+    fn index(&self, index: usize) -> &T {
+        match index {
+            0 => &self.0,
+            1 => &self.1,
+            _ => panic!("index out of bounds: a Vector2 has 2 components but the index is {index}"),
+        }
+    }
+}
+
+impl<T> IndexMut<usize> for Vector2<T> {
+    fn index_mut(&mut self, index: usize) -> &mut T {
+        match index {
+            0 => &mut self.0,
+            1 => &mut self.1,
+            _ => panic!("index out of bounds: a Vector2 has 2 components but the index is {index}"),
+        }
     }
 }
 
-impl Add<Vector2<f32>> for Vector2<f32> {
-    type Output = Vector2<f32>;
+impl<T: Scalar> Add<Vector2<T>> for Vector2<T> {
+    type Output = Vector2<T>;
 
-    fn add(self, rhs: Vector2<f32>) -> Self::Output {
+    fn add(self, rhs: Vector2<T>) -> Self::Output {
         Self(self.0 + rhs.0, self.1 + rhs.1)
     }
 }
 
-impl Sub<Vector2<f32>> for Vector2<f32> {
-    type Output = Vector2<f32>;
+impl<T: Scalar> Sub<Vector2<T>> for Vector2<T> {
+    type Output = Vector2<T>;
 
-    fn sub(self, rhs: Vector2<f32>) -> Self::Output {
+    fn sub(self, rhs: Vector2<T>) -> Self::Output {
         Self(self.0 - rhs.0, self.1 - rhs.1)
     }
 }
 
-impl Mul<f32> for Vector2<f32> {
-    type Output = Vector2<f32>;
+impl<T: Scalar> Mul<T> for Vector2<T> {
+    type Output = Vector2<T>;
 
-    fn mul(self, rhs: f32) -> Self::Output {
+    fn mul(self, rhs: T) -> Self::Output {
         Self(self.0 * rhs, self.1 * rhs)
     }
 }
 
-impl Div<f32> for Vector2<f32> {
-    type Output = Vector2<f32>;
+impl<T: Scalar> Div<T> for Vector2<T> {
+    type Output = Vector2<T>;
 
-    fn div(self, rhs: f32) -> Self::Output {
+    fn div(self, rhs: T) -> Self::Output {
         Self(self.0 / rhs, self.1 / rhs)
     }
 }
 
-impl AddAssign<Vector2<f32>> for Vector2<f32> {
-    fn add_assign(&mut self, rhs: Vector2<f32>) {
+impl<T: Scalar + Neg<Output = T>> Neg for Vector2<T> {
+    type Output = Vector2<T>;
+
+    fn neg(self) -> Self::Output {
+        Self(-self.0, -self.1)
+    }
+}
+
+/// Sums from the zero vector, so an empty iterator sums to zero.
+impl<T: Scalar + Default> Sum for Vector2<T> {
+    fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.fold(Vector2(T::default(), T::default()), |acc, v| acc + v)
+    }
+}
+
+impl<'a, T: Scalar + Default> Sum<&'a Vector2<T>> for Vector2<T> {
+    fn sum<I: Iterator<Item = &'a Self>>(iter: I) -> Self {
+        iter.fold(Vector2(T::default(), T::default()), |acc, v| {
+            Vector2(acc.0 + v.0, acc.1 + v.1)
+        })
+    }
+}
+
+impl<T: Scalar> AddAssign<Vector2<T>> for Vector2<T> {
+    fn add_assign(&mut self, rhs: Vector2<T>) {
         self.0 += rhs.0;
         self.1 += rhs.1;
     }
 }
 
-impl SubAssign<Vector2<f32>> for Vector2<f32> {
-    fn sub_assign(&mut self, rhs: Vector2<f32>) {
+impl<T: Scalar> SubAssign<Vector2<T>> for Vector2<T> {
+    fn sub_assign(&mut self, rhs: Vector2<T>) {
         self.0 -= rhs.0;
         self.1 -= rhs.1;
     }
 }
 
-impl MulAssign<Vector2<f32>> for Vector2<f32> {
-    fn mul_assign(&mut self, rhs: Vector2<f32>) {
+impl<T: Scalar> MulAssign<Vector2<T>> for Vector2<T> {
+    fn mul_assign(&mut self, rhs: Vector2<T>) {
         self.0 *= rhs.0;
         self.1 *= rhs.1;
     }
 }
 
-impl DivAssign<Vector2<f32>> for Vector2<f32> {
-    fn div_assign(&mut self, rhs: Vector2<f32>) {
+impl<T: Scalar> DivAssign<Vector2<T>> for Vector2<T> {
+    fn div_assign(&mut self, rhs: Vector2<T>) {
         self.0 /= rhs.0;
         self.1 /= rhs.1;
     }
 }
 
-impl AddAssign<f32> for Vector2<f32> {
-    fn add_assign(&mut self, rhs: f32) {
+impl<T: Scalar> AddAssign<T> for Vector2<T> {
+    fn add_assign(&mut self, rhs: T) {
         self.0 += rhs;
         self.1 += rhs;
     }
 }
 
-impl SubAssign<f32> for Vector2<f32> {
-    fn sub_assign(&mut self, rhs: f32) {
+impl<T: Scalar> SubAssign<T> for Vector2<T> {
+    fn sub_assign(&mut self, rhs: T) {
         self.0 -= rhs;
         self.1 -= rhs;
     }
 }
 
-impl MulAssign<f32> for Vector2<f32> {
-    fn mul_assign(&mut self, rhs: f32) {
+impl<T: Scalar> MulAssign<T> for Vector2<T> {
+    fn mul_assign(&mut self, rhs: T) {
         self.0 *= rhs;
         self.1 *= rhs;
     }
 }
 
-impl DivAssign<f32> for Vector2<f32> {
-    fn div_assign(&mut self, rhs: f32) {
+impl<T: Scalar> DivAssign<T> for Vector2<T> {
+    fn div_assign(&mut self, rhs: T) {
         self.0 /= rhs;
         self.1 /= rhs;
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn normalising_zero_does_not_produce_nan() {
+        let mut zero = Vector2::<f32>::ZERO;
+        zero.normalise();
+        assert_eq!(zero, Vector2::ZERO);
+
+        assert_eq!(Vector2(0.0, 0.0).normalised(), Vector2::ZERO);
+        assert_eq!(Vector2(1e-8, -1e-8).to_normalised(), Vector2::ZERO);
+    }
+
+    #[test]
+    fn normalising_gives_unit_length() {
+        let v = Vector2(3.0, 4.0).normalised();
+
+        assert!((v.len() - 1.0).abs() < 1e-6);
+        assert!(v.approx_eq(&Vector2(0.6, 0.8), 1e-6));
+    }
+
+    #[test]
+    fn approx_eq_tolerates_rounding() {
+        let rotated = Vector2(1.0, 0.0).rotate(std::f32::consts::FRAC_PI_2);
+
+        assert_ne!(rotated, Vector2::UP);
+        assert!(rotated.approx_eq(&Vector2::UP, 1e-6));
+        assert!(!rotated.approx_eq(&Vector2::RIGHT, 1e-6));
+        assert_eq!(Vector2(3, -2), Vector2(3, -2));
+    }
+
+    #[test]
+    fn rotating_a_quarter_turn() {
+        let rotated = Vector2::RIGHT.rotate(std::f32::consts::FRAC_PI_2);
+
+        assert!(rotated.approx_eq(&Vector2::UP, 1e-6), "{rotated}");
+    }
+
+    #[test]
+    fn rotating_a_half_turn() {
+        let rotated = Vector2::RIGHT.rotate(std::f32::consts::PI);
+
+        assert!(rotated.approx_eq(&Vector2::LEFT, 1e-6), "{rotated}");
+    }
+
+    #[test]
+    fn rotating_a_full_turn() {
+        let rotated = Vector2::RIGHT.rotate(std::f32::consts::TAU);
+
+        assert!(rotated.approx_eq(&Vector2::RIGHT, 1e-6), "{rotated}");
+    }
+
+    #[test]
+    fn rotating_around_a_pivot() {
+        let rotated = Vector2(3.0, 1.0).rotate_around(&Vector2(1.0, 1.0), std::f32::consts::PI);
+
+        assert!(rotated.approx_eq(&Vector2(-1.0, 1.0), 1e-6), "{rotated}");
+    }
+
+    #[test]
+    fn from_angle_round_trips_through_angle() {
+        use std::f32::consts::PI;
+
+        for radians in [0.0, 0.5, PI / 2.0, 3.0, -PI / 4.0, -3.0] {
+            let v = Vector2::from_angle(radians);
+
+            assert!((v.len() - 1.0).abs() < 1e-6);
+            assert!((v.angle() - radians).abs() < 1e-6, "{radians}");
+        }
+        assert_eq!(Vector2::splat(2.5), Vector2(2.5, 2.5));
+        assert_eq!(Vector2::splat(-3), Vector2(-3, -3));
+    }
+
+    #[test]
+    fn angles_cover_every_quadrant() {
+        use std::f32::consts::{FRAC_PI_4, PI};
+
+        assert_eq!(Vector2::<f32>::ZERO.angle(), 0.0);
+        for (v, expected) in [
+            (Vector2(1.0, 1.0), FRAC_PI_4),
+            (Vector2(-1.0, 1.0), 3.0 * FRAC_PI_4),
+            (Vector2(-1.0, -1.0), -3.0 * FRAC_PI_4),
+            (Vector2(1.0, -1.0), -FRAC_PI_4),
+            (Vector2(-1.0, 0.0), PI),
+            (Vector2(-1.0, -0.0), -PI),
+        ] {
+            let angle = v.angle();
+
+            assert!((angle - expected).abs() < 1e-6, "{v}: {angle}");
+            assert!((-PI..=PI).contains(&angle));
+        }
+    }
+
+    #[test]
+    fn angle_between_is_signed_and_handles_zero() {
+        use std::f32::consts::{FRAC_PI_2, PI};
+
+        assert_eq!(Vector2::ZERO.angle_between(&Vector2::UP), 0.0);
+        assert_eq!(Vector2::UP.angle_between(&Vector2::ZERO), 0.0);
+        assert!((Vector2::RIGHT.angle_between(&Vector2::UP) - FRAC_PI_2).abs() < 1e-6);
+        assert!((Vector2::UP.angle_between(&Vector2::RIGHT) + FRAC_PI_2).abs() < 1e-6);
+        assert!((Vector2(1.0, 1.0).angle_between(&Vector2(-1.0, -1.0)).abs() - PI).abs() < 1e-6);
+        assert!((Vector2::LEFT.angle_between(&Vector2::RIGHT).abs() - PI).abs() < 1e-6);
+    }
+
+    #[test]
+    fn polar_round_trip() {
+        for (radius, angle) in [(1.0, 0.0), (2.5, 1.0), (10.0, -2.0), (0.5, 3.0)] {
+            let v = Vector2::from_polar(radius, angle);
+            let (r, a) = v.to_polar();
+
+            assert!((r - radius).abs() < 1e-5, "{v}");
+            assert!((a - angle).abs() < 1e-6, "{v}");
+            assert!(Vector2::from_polar(r, a).approx_eq(&v, 1e-5));
+        }
+        assert!(Vector2::from_polar(2.0, std::f32::consts::FRAC_PI_2)
+            .approx_eq(&Vector2(0.0, 2.0), 1e-6));
+        assert_eq!(Vector2::ZERO.to_polar(), (0.0, 0.0));
+    }
+
+    #[test]
+    fn component_wise_min_max_and_clamp() {
+        let a = Vector2(1.0, 5.0);
+        let b = Vector2(3.0, -2.0);
+
+        assert_eq!(a.min(&b), Vector2(1.0, -2.0));
+        assert_eq!(a.max(&b), Vector2(3.0, 5.0));
+
+        let lo = Vector2(-10, 0);
+        let hi = Vector2(10, 4);
+        assert_eq!(Vector2(-20, 2).clamp(&lo, &hi), Vector2(-10, 2));
+        assert_eq!(Vector2(5, 9).clamp(&lo, &hi), Vector2(5, 4));
+        assert_eq!(Vector2(3, 3).clamp(&lo, &hi), Vector2(3, 3));
+    }
+
+    #[test]
+    fn reflect_off_horizontal_and_vertical_surfaces() {
+        let velocity = Vector2(2.0, -3.0);
+
+        // Floor
+        assert_eq!(velocity.reflect(&Vector2::UP), Vector2(2.0, 3.0));
+        // Right wall
+        assert_eq!(velocity.reflect(&Vector2::LEFT), Vector2(-2.0, -3.0));
+        // Parallel to the surface, unchanged
+        assert_eq!(Vector2(4.0, 0.0).reflect(&Vector2::UP), Vector2(4.0, 0.0));
+
+        let diagonal = Vector2(1.0, 1.0).normalised();
+        assert!(Vector2(-1.0, 0.0)
+            .reflect(&diagonal)
+            .approx_eq(&Vector2(0.0, 1.0), 1e-6));
+    }
+
+    #[test]
+    fn project_and_reject() {
+        let v = Vector2(3.0, 4.0);
+        let axis = Vector2(2.0, 0.0);
+
+        assert_eq!(v.project_onto(&axis), Vector2(3.0, 0.0));
+        assert_eq!(v.reject_from(&axis), Vector2(0.0, 4.0));
+
+        let diagonal = Vector2(1.0, 1.0);
+        let sum = v.project_onto(&diagonal) + v.reject_from(&diagonal);
+        assert!(sum.approx_eq(&v, 1e-6));
+        assert!(v.reject_from(&diagonal).dot(&diagonal).abs() < 1e-6);
+
+        assert_eq!(v.project_onto(&Vector2::ZERO), Vector2::ZERO);
+        assert_eq!(v.reject_from(&Vector2::ZERO), Vector2::ZERO);
+    }
+
+    #[test]
+    fn perp_dot_gives_the_turn_direction() {
+        assert_eq!(Vector2::RIGHT.perp_dot(&Vector2::UP), 1.0);
+        assert_eq!(Vector2::UP.perp_dot(&Vector2::RIGHT), -1.0);
+        assert_eq!(Vector2(2.0, 4.0).perp_dot(&Vector2(1.0, 2.0)), 0.0);
+        assert_eq!(Vector2(3, 1).perp_dot(&Vector2(2, 5)), 13);
+    }
+
+    #[test]
+    fn perpendicular_turns_counter_clockwise() {
+        assert_eq!(Vector2::RIGHT.perpendicular(), Vector2::UP);
+        assert_eq!(Vector2::UP.perpendicular(), Vector2::LEFT);
+
+        let v = Vector2(3, -2);
+        assert_eq!(v.perpendicular(), Vector2(2, 3));
+        assert_eq!(v.dot(&v.perpendicular()), 0);
+        assert_eq!(v.perpendicular().len_squared(), v.len_squared());
+    }
+
+    #[test]
+    fn indexes_components_by_axis() {
+        let mut v = Vector2(3.0, -1.0);
+        assert_eq!((v[0], v[1]), (3.0, -1.0));
+
+        for axis in 0..2 {
+            v[axis] *= 2.0;
+        }
+        assert_eq!(v, Vector2(6.0, -2.0));
+    }
+
+    #[test]
+    #[should_panic]
+    fn indexing_past_y_panics() {
+        let v = Vector2(1, 2);
+        let _ = v[2];
+    }
+
+    #[test]
+    fn lerp_interpolates_and_extrapolates() {
+        let a = Vector2(1.0, 2.0);
+        let b = Vector2(3.0, -2.0);
+
+        assert_eq!(a.lerp(&b, 0.0), a);
+        assert_eq!(a.lerp(&b, 0.25), Vector2(1.5, 1.0));
+        assert_eq!(a.lerp(&b, 1.0), b);
+        assert_eq!(a.lerp(&b, -1.0), Vector2(-1.0, 6.0));
+        assert_eq!(a.lerp(&b, 2.0), Vector2(5.0, -6.0));
+    }
+
+    #[test]
+    fn midpoint_is_halfway() {
+        let a = Vector2(-2.0, 1.0);
+        let b = Vector2(4.0, 6.0);
+
+        assert_eq!(a.midpoint(&b), Vector2(1.0, 3.5));
+        assert_eq!(b.midpoint(&a), a.lerp(&b, 0.5));
+        assert_eq!(a.midpoint(&a), a);
+    }
+
+    #[test]
+    fn grid_distances() {
+        let pairs = [
+            (Vector2(0.0, 0.0), Vector2(3.0, 4.0), 7.0, 4.0),
+            (Vector2(1.0, -1.0), Vector2(-2.0, 1.0), 5.0, 3.0),
+            (Vector2(2.5, 2.5), Vector2(2.5, 2.5), 0.0, 0.0),
+        ];
+
+        for (a, b, manhattan, chebyshev) in pairs {
+            assert_eq!(a.manhattan_distance(&b), manhattan);
+            assert_eq!(b.manhattan_distance(&a), manhattan);
+            assert_eq!(a.chebyshev_distance(&b), chebyshev);
+        }
+    }
+
+    #[test]
+    fn component_wise_rounding() {
+        let v = Vector2(-1.5, 2.25);
+
+        assert_eq!(v.abs(), Vector2(1.5, 2.25));
+        assert_eq!(v.floor(), Vector2(-2.0, 2.0));
+        assert_eq!(v.ceil(), Vector2(-1.0, 3.0));
+        assert_eq!(v.round(), Vector2(-2.0, 2.0));
+
+        // Halfway cases round away from zero
+        assert_eq!(Vector2(0.5, 2.5).round(), Vector2(1.0, 3.0));
+        assert_eq!(Vector2(-0.5, -2.5).round(), Vector2(-1.0, -3.0));
+        assert_eq!(Vector2(0.49, -0.49).round(), Vector2(0.0, -0.0));
+    }
+
+    #[test]
+    fn with_replaces_one_component() {
+        let v = Vector2(1.5, -2.0);
+
+        assert_eq!(v.with_x(4.0), Vector2(4.0, -2.0));
+        assert_eq!(v.with_y(0.0), Vector2(1.5, 0.0));
+        assert_eq!(v, Vector2(1.5, -2.0));
+        assert_eq!(Vector2(1, 2).with_y(7), Vector2(1, 7));
+    }
+
+    #[test]
+    fn map_applies_to_both_components() {
+        let v = Vector2(-3.0, 12.5);
+
+        assert_eq!(v.map(f32::abs), Vector2(3.0, 12.5));
+        assert_eq!(v.map(|c| c.clamp(0.0, 10.0)), Vector2(0.0, 10.0));
+        assert_eq!(Vector2(2, 5).map(|c| c * c), Vector2(4, 25));
+    }
+
+    #[test]
+    fn sums_points_into_a_centroid() {
+        let points = [Vector2(0.0, 0.0), Vector2(4.0, 0.0), Vector2(2.0, 6.0)];
+
+        let by_value: Vector2<f32> = points.iter().cloned().sum();
+        let by_ref: Vector2<f32> = points.iter().sum();
+        assert_eq!(by_value, Vector2(6.0, 6.0));
+        assert_eq!(by_ref / points.len() as f32, Vector2(2.0, 2.0));
+
+        assert_eq!(
+            Vec::<Vector2<f32>>::new().into_iter().sum::<Vector2<f32>>(),
+            Vector2::ZERO
+        );
+        assert_eq!(
+            [Vector2(1, 2), Vector2(3, 4)].iter().sum::<Vector2<i32>>(),
+            Vector2(4, 6)
+        );
+    }
+
+    #[test]
+    fn display_respects_precision() {
+        let v = Vector2(1.0, -2.5);
+
+        assert_eq!(v.to_string(), "(1, -2.5)");
+        assert_eq!(format!("{:.2}", v), "(1.00, -2.50)");
+        assert_eq!(Vector2(3, 4).to_string(), "(3, 4)");
+    }
+
+    #[test]
+    fn converts_to_and_from_tuples_and_arrays() {
+        let v: Vector2<f32> = (3.0, 4.0).into();
+        assert_eq!(v, Vector2(3.0, 4.0));
+        assert_eq!(<(f32, f32)>::from(v), (3.0, 4.0));
+
+        let v: Vector2<f32> = [1.5, -2.0].into();
+        assert_eq!(v, Vector2(1.5, -2.0));
+        assert_eq!(<[f32; 2]>::from(v), [1.5, -2.0]);
+    }
+
+    #[test]
+    fn integer_arithmetic() {
+        let a = Vector2(3, -2);
+        let b = Vector2(1, 5);
+
+        assert_eq!(a.clone() + b.clone(), Vector2(4, 3));
+        assert_eq!(a.clone() - b.clone(), Vector2(2, -7));
+        assert_eq!(a.clone() * 3, Vector2(9, -6));
+        assert_eq!(a.clone() / 2, Vector2(1, -1));
+        assert_eq!(-a.clone(), Vector2(-3, 2));
+        assert_eq!(a.dot(&b), -7);
+        assert_eq!(a.len_squared(), 13);
+        assert_eq!(a.distance_squared(&b), 53);
+
+        let mut c = a;
+        c += b;
+        c *= 2;
+        c -= 1;
+        assert_eq!(c, Vector2(7, 5));
+    }
+}